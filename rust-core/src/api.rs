@@ -2,11 +2,28 @@
 //! 
 //! HTTP API for BHCS Rust Core
 
+use std::sync::Arc;
+use std::time::Duration;
+use serde::Deserialize;
 use serde_json::{json, Value};
 use warp::{Filter, Rejection, Reply};
+use crate::influence_queue::{InfluenceQueue, InfluenceRequest};
 use crate::{HomeostaticEngine, HomeostaticConfig};
 
+const DEFAULT_WATCH_TIMEOUT_MS: u64 = 30_000;
+const SNAPSHOT_PATH: &str = "engine_snapshot.cbor";
+const SNAPSHOT_AUTOSAVE_INTERVAL: Duration = Duration::from_secs(60);
+const INFLUENCE_DEBOUNCE: Duration = Duration::from_millis(250);
+const INFLUENCE_TICK: Duration = Duration::from_millis(250);
+
 pub fn start_server(engine: std::sync::Arc<std::sync::Mutex<HomeostaticEngine>>) {
+    crate::snapshot::spawn_autosave(engine.clone(), SNAPSHOT_PATH.to_string(), SNAPSHOT_AUTOSAVE_INTERVAL);
+
+    let influence_queue = Arc::new(InfluenceQueue::new(INFLUENCE_DEBOUNCE));
+    crate::influence_queue::spawn_scheduler(engine.clone(), influence_queue.clone(), INFLUENCE_TICK);
+    let influence_queue = warp::any().map(move || influence_queue.clone());
+
+    let shutdown_engine = engine.clone();
     let engine = warp::any()
         .map(move || engine.clone());
 
@@ -16,24 +33,68 @@ pub fn start_server(engine: std::sync::Arc<std::sync::Mutex<HomeostaticEngine>>)
         .and(engine.clone())
         .and_then(get_state);
 
+    // GET /watch?since=<version>&timeout_ms=<ms> - long-polls until a zone's
+    // state crosses a boundary after `since`, or the timeout elapses
+    let watch_route = warp::path("watch")
+        .and(warp::get())
+        .and(warp::query::<WatchQuery>())
+        .and(engine.clone())
+        .and_then(watch_zones);
+
     // GET /health - System health check
     let health_route = warp::path("health")
         .and(warp::get())
         .and(engine.clone())
         .and_then(health_check);
 
-    // POST /influence - Apply influence to zone
+    // POST /influence - Queue a debounced influence on a zone
     let influence_route = warp::path("influence")
         .and(warp::post())
         .and(warp::body::json())
         .and(engine.clone())
+        .and(influence_queue.clone())
         .and_then(apply_influence);
 
-    let routes = state_route.or(health_route).or(influence_route);
+    // POST /influence/batch - Queue a batch of debounced influences atomically
+    let influence_batch_route = warp::path!("influence" / "batch")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(engine.clone())
+        .and(influence_queue.clone())
+        .and_then(apply_influence_batch);
+
+    // POST /snapshot - force an immediate CBOR save of the current engine state
+    let snapshot_post_route = warp::path("snapshot")
+        .and(warp::post())
+        .and(engine.clone())
+        .and_then(save_snapshot);
+
+    // GET /snapshot - download the current engine state as a CBOR blob
+    let snapshot_get_route = warp::path("snapshot")
+        .and(warp::get())
+        .and(engine.clone())
+        .and_then(get_snapshot);
+
+    let routes = state_route
+        .or(watch_route)
+        .or(health_route)
+        .or(influence_route)
+        .or(influence_batch_route)
+        .or(snapshot_post_route)
+        .or(snapshot_get_route);
+
+    let (_addr, server) = warp::serve(routes)
+        .bind_with_graceful_shutdown(([127, 0, 0, 1], 3030), async {
+            tokio::signal::ctrl_c().await.ok();
+        });
+    server.await;
 
-    warp::serve(routes)
-        .run(([127, 0, 0, 1], 3030))
-        .await;
+    // Save on clean shutdown so a restart can rehydrate instead of
+    // randomizing zone activity from scratch.
+    let snapshot = shutdown_engine.lock().unwrap().snapshot();
+    if let Err(e) = crate::snapshot::save(&snapshot, SNAPSHOT_PATH) {
+        eprintln!("failed to save snapshot on shutdown: {e}");
+    }
 }
 
 async fn get_state(
@@ -47,7 +108,8 @@ async fn get_state(
                 "id": zone.id(),
                 "activity": zone.activity(),
                 "state": format!("{:?}", zone.state()),
-                "target": zone.target()
+                "target": zone.target(),
+                "neighbor_avg_activity": engine.neighbor_average_activity(zone.id())
             })
         })
         .collect();
@@ -60,6 +122,72 @@ async fn get_state(
     Ok(warp::reply::json(&response))
 }
 
+#[derive(Debug, Deserialize)]
+struct WatchQuery {
+    since: u64,
+    timeout_ms: Option<u64>,
+}
+
+async fn watch_zones(
+    query: WatchQuery,
+    engine: std::sync::Arc<std::sync::Mutex<HomeostaticEngine>>,
+) -> Result<impl Reply, Rejection> {
+    let timeout = Duration::from_millis(query.timeout_ms.unwrap_or(DEFAULT_WATCH_TIMEOUT_MS));
+
+    let (notify, version) = {
+        let engine = engine.lock().unwrap();
+        (engine.watch_notify(), engine.version())
+    };
+
+    if version <= query.since {
+        // The wakeup for a transition landing between the check above and
+        // this await is missed; this request then returns it once the
+        // timeout elapses or the next transition fires.
+        let _ = tokio::time::timeout(timeout, notify.notified()).await;
+    }
+
+    let engine = engine.lock().unwrap();
+    let zones: Vec<Value> = engine.zones_changed_since(query.since)
+        .iter()
+        .map(|zone| {
+            json!({
+                "id": zone.id(),
+                "activity": zone.activity(),
+                "state": format!("{:?}", zone.state()),
+                "target": zone.target()
+            })
+        })
+        .collect();
+
+    let response = json!({
+        "version": engine.version(),
+        "zones": zones
+    });
+
+    Ok(warp::reply::json(&response))
+}
+
+async fn save_snapshot(
+    engine: std::sync::Arc<std::sync::Mutex<HomeostaticEngine>>,
+) -> Result<impl Reply, Rejection> {
+    let snapshot = engine.lock().unwrap().snapshot();
+    let response = match crate::snapshot::save(&snapshot, SNAPSHOT_PATH) {
+        Ok(()) => json!({ "success": true, "path": SNAPSHOT_PATH }),
+        Err(e) => json!({ "success": false, "error": e }),
+    };
+
+    Ok(warp::reply::json(&response))
+}
+
+async fn get_snapshot(
+    engine: std::sync::Arc<std::sync::Mutex<HomeostaticEngine>>,
+) -> Result<impl Reply, Rejection> {
+    let snapshot = engine.lock().unwrap().snapshot();
+    let bytes = serde_cbor::to_vec(&snapshot).map_err(|_| warp::reject::reject())?;
+
+    Ok(warp::reply::with_header(bytes, "content-type", "application/cbor"))
+}
+
 async fn health_check(
     engine: std::sync::Arc<std::sync::Mutex<HomeostaticEngine>>,
 ) -> Result<impl Reply, Rejection> {
@@ -75,22 +203,54 @@ async fn health_check(
 }
 
 async fn apply_influence(
-    body: Value,
+    request: InfluenceRequest,
     engine: std::sync::Arc<std::sync::Mutex<HomeostaticEngine>>,
+    queue: Arc<InfluenceQueue>,
 ) -> Result<impl Reply, Rejection> {
-    let zone_id = body["zone_id"].as_u64().unwrap_or(0) as usize;
-    let influence = body["influence"].as_f64().unwrap_or(0.0);
-
-    {
-        let mut engine = engine.lock().unwrap();
-        engine.apply_influence(zone_id, influence);
+    let known = engine.lock().unwrap().get_zone(request.zone_id).is_some();
+    if known {
+        queue.enqueue(request.clone());
     }
 
     let response = json!({
-        "success": true,
-        "zone_id": zone_id,
-        "influence": influence
+        "success": known,
+        "zone_id": request.zone_id,
+        "influence": request.influence
     });
 
     Ok(warp::reply::json(&response))
 }
+
+#[derive(Debug, Deserialize)]
+struct InfluenceBatchRequest {
+    batch: Vec<InfluenceRequest>,
+}
+
+async fn apply_influence_batch(
+    body: InfluenceBatchRequest,
+    engine: std::sync::Arc<std::sync::Mutex<HomeostaticEngine>>,
+    queue: Arc<InfluenceQueue>,
+) -> Result<impl Reply, Rejection> {
+    let engine = engine.lock().unwrap();
+    let results: Vec<Value> = body.batch.into_iter()
+        .map(|request| {
+            if engine.get_zone(request.zone_id).is_some() {
+                let result = json!({
+                    "zone_id": request.zone_id,
+                    "influence": request.influence,
+                    "success": true
+                });
+                queue.enqueue(request);
+                result
+            } else {
+                json!({
+                    "zone_id": request.zone_id,
+                    "success": false,
+                    "error": "zone not found"
+                })
+            }
+        })
+        .collect();
+
+    Ok(warp::reply::json(&json!({ "results": results })))
+}
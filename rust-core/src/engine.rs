@@ -2,22 +2,38 @@
 //! 
 //! Core regulation logic for BHCS deterministic control
 
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
-use crate::zone::Zone;
+use tokio::sync::Notify;
+use crate::manifest::ZoneManifest;
+use crate::snapshot::EngineSnapshot;
+use crate::zone::{PidController, Zone};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HomeostaticConfig {
     pub target_calmness: f64,
-    pub learning_rate: f64,
+    pub kp: f64,
+    pub ki: f64,
+    pub kd: f64,
+    pub integral_max: f64,
+    pub dt: f64,
     pub zones: usize,
+    // Coupling strength for the inter-zone diffusion term; 0.0 (the
+    // default) makes zones behave as fully independent controllers.
+    pub diffusion: f64,
 }
 
 impl Default for HomeostaticConfig {
     fn default() -> Self {
         Self {
             target_calmness: 0.5,
-            learning_rate: 0.02,
+            kp: 0.02, // matches the old proportional-only learning rate
+            ki: 0.0,
+            kd: 0.0,
+            integral_max: 1.0,
+            dt: 1.0,
             zones: 5,
+            diffusion: 0.0,
         }
     }
 }
@@ -26,6 +42,18 @@ impl Default for HomeostaticConfig {
 pub struct HomeostaticEngine {
     zones: Vec<Zone>,
     config: HomeostaticConfig,
+    pid_controllers: Vec<PidController>,
+    // EMA-smoothed activity per zone, so the controller's derivative term
+    // sees a one-shot influence spike as a ramp rather than a step.
+    ema: Vec<f64>,
+    // Adjacency list per zone (indexed the same as `zones`), for the
+    // reaction-diffusion coupling term in `update()`.
+    neighbors: Vec<Vec<usize>>,
+    // Bumped each time any zone's `state` actually transitions (not every
+    // tick), so `/watch` clients can chain requests off a single counter
+    // instead of diffing the full zone list.
+    version: u64,
+    watch_notify: Arc<Notify>,
 }
 
 impl HomeostaticEngine {
@@ -34,19 +62,180 @@ impl HomeostaticEngine {
         for i in 0..config.zones {
             zones.push(Zone::new(i));
         }
-        
-        Self { zones, config }
+
+        let ema = zones.iter().map(|z| z.activity()).collect();
+        let pid_controllers = zones.iter()
+            .map(|_| PidController::with_integral_max(config.kp, config.ki, config.kd, config.integral_max))
+            .collect();
+        let neighbors = vec![Vec::new(); zones.len()];
+
+        Self {
+            zones,
+            config,
+            pid_controllers,
+            ema,
+            neighbors,
+            version: 0,
+            watch_notify: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Builds a data-driven engine from a TOML manifest (see
+    /// [`HomeostaticConfig::from_toml`]) instead of [`Self::new`]'s five
+    /// hardcoded zones.
+    pub fn from_manifest(path: &str, profile: Option<&str>) -> Result<Self, String> {
+        let (config, zone_defs) = HomeostaticConfig::from_toml(path, profile)?;
+        Self::from_zone_manifests(config, &zone_defs)
+    }
+
+    fn from_zone_manifests(config: HomeostaticConfig, zone_defs: &[ZoneManifest]) -> Result<Self, String> {
+        for z in zone_defs {
+            for &n in &z.neighbors {
+                if n >= zone_defs.len() {
+                    return Err(format!(
+                        "zone {} lists neighbor {n}, but the manifest only defines {} zones",
+                        z.id, zone_defs.len()
+                    ));
+                }
+            }
+        }
+
+        let zones: Vec<Zone> = zone_defs.iter()
+            .map(|z| Zone::from_manifest(z.id, z.name.clone(), z.initial_activity, z.target.unwrap_or(config.target_calmness)))
+            .collect();
+
+        let ema = zones.iter().map(|z| z.activity()).collect();
+        let pid_controllers = zones.iter()
+            .map(|_| PidController::with_integral_max(config.kp, config.ki, config.kd, config.integral_max))
+            .collect();
+        let neighbors = zone_defs.iter().map(|z| z.neighbors.clone()).collect();
+
+        Ok(Self {
+            zones,
+            config,
+            pid_controllers,
+            ema,
+            neighbors,
+            version: 0,
+            watch_notify: Arc::new(Notify::new()),
+        })
+    }
+
+    /// Captures full engine state for [`crate::snapshot::save`].
+    pub fn snapshot(&self) -> EngineSnapshot {
+        EngineSnapshot {
+            zones: self.zones.clone(),
+            ema: self.ema.clone(),
+            pid_controllers: self.pid_controllers.clone(),
+            neighbors: self.neighbors.clone(),
+            version: self.version,
+            config: self.config.clone(),
+        }
+    }
+
+    /// Rehydrates an engine from a snapshot file at `path`, or `None` if
+    /// none exists yet, instead of [`Self::new`]'s randomized zones.
+    pub fn restore_from(path: &str) -> Result<Option<Self>, String> {
+        let Some(snapshot) = crate::snapshot::load(path)? else {
+            return Ok(None);
+        };
+        Ok(Some(Self::from_snapshot(snapshot)))
+    }
+
+    fn from_snapshot(snapshot: EngineSnapshot) -> Self {
+        let neighbors = if snapshot.neighbors.len() == snapshot.zones.len() {
+            snapshot.neighbors
+        } else {
+            vec![Vec::new(); snapshot.zones.len()]
+        };
+
+        Self {
+            zones: snapshot.zones,
+            config: snapshot.config,
+            pid_controllers: snapshot.pid_controllers,
+            ema: snapshot.ema,
+            neighbors,
+            version: snapshot.version,
+            watch_notify: Arc::new(Notify::new()),
+        }
     }
 
     pub fn update(&mut self) {
-        for zone in &mut self.zones {
-            // Apply homeostatic update equation
-            let error = self.config.target_calmness - zone.activity();
-            let adjustment = self.config.learning_rate * error;
-            zone.apply_adjustment(adjustment);
+        let mut transitioned = false;
+        // Snapshot pre-tick activity so the diffusion term is computed
+        // simultaneously across all zones instead of order-dependently
+        // mixing already-updated neighbors into later zones.
+        let previous_activity: Vec<f64> = self.zones.iter().map(|z| z.activity()).collect();
+
+        for (i, zone) in self.zones.iter_mut().enumerate() {
+            let before = zone.state().clone();
+
+            let diffusion = Self::diffusion_term(self.config.diffusion, i, &self.neighbors[i], &previous_activity);
+
+            self.ema[i] = 0.97 * self.ema[i] + 0.03 * zone.activity();
+            let pid_adjustment = self.pid_controllers[i].step_dt(
+                self.ema[i],
+                self.config.target_calmness,
+                self.config.dt,
+            );
+            zone.apply_adjustment(diffusion + pid_adjustment);
+
+            if *zone.state() != before {
+                self.version += 1;
+                zone.mark_transitioned(self.version);
+                transitioned = true;
+            }
+        }
+
+        if transitioned {
+            self.watch_notify.notify_waiters();
         }
     }
 
+    // `diffusion * sum(neighbor_activity - self_activity) / degree`, computed
+    // from the pre-tick `previous_activity` snapshot; 0.0 for an isolated zone.
+    fn diffusion_term(diffusion: f64, zone_index: usize, neighbors: &[usize], previous_activity: &[f64]) -> f64 {
+        if neighbors.is_empty() {
+            return 0.0;
+        }
+
+        let self_activity = previous_activity[zone_index];
+        let spread: f64 = neighbors.iter()
+            .map(|&n| previous_activity[n] - self_activity)
+            .sum();
+
+        diffusion * spread / neighbors.len() as f64
+    }
+
+    /// Average current activity of `zone_id`'s neighbors, or
+    /// `None` for an isolated zone or unknown id. Exposed on `/state` so
+    /// operators can see the coupling pulling a zone before the PID term
+    /// responds to it.
+    pub fn neighbor_average_activity(&self, zone_id: usize) -> Option<f64> {
+        let neighbors = self.neighbors.get(zone_id)?;
+        if neighbors.is_empty() {
+            return None;
+        }
+
+        let sum: f64 = neighbors.iter().map(|&n| self.zones[n].activity()).sum();
+        Some(sum / neighbors.len() as f64)
+    }
+
+    /// Current state-transition version, for `/watch` clients to chain off.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Handle other tasks can await to learn about the next state transition.
+    pub fn watch_notify(&self) -> Arc<Notify> {
+        self.watch_notify.clone()
+    }
+
+    /// Zones whose state has transitioned since `since`.
+    pub fn zones_changed_since(&self, since: u64) -> Vec<&Zone> {
+        self.zones.iter().filter(|z| z.state_version() > since).collect()
+    }
+
     pub fn apply_influence(&mut self, zone_id: usize, influence: f64) {
         if let Some(zone) = self.zones.get_mut(zone_id) {
             zone.apply_influence(influence);
@@ -84,3 +273,64 @@ pub struct SystemMetrics {
     pub homeostatic_balance: f64,
     pub timestamp: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffusion_term_is_zero_for_isolated_zone() {
+        let activity = vec![0.2, 0.9];
+        assert_eq!(HomeostaticEngine::diffusion_term(1.0, 0, &[], &activity), 0.0);
+    }
+
+    #[test]
+    fn diffusion_term_pulls_toward_neighbor_average() {
+        let activity = vec![0.2, 0.6, 1.0];
+        // Zone 0 (0.2) sits below both neighbors (0.6, 1.0); the term should
+        // be positive, pulling it up.
+        let term = HomeostaticEngine::diffusion_term(0.5, 0, &[1, 2], &activity);
+        let expected = 0.5 * ((0.6 - 0.2) + (1.0 - 0.2)) / 2.0;
+        assert!((term - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn diffusion_term_is_zero_when_activity_matches_neighbors() {
+        let activity = vec![0.5, 0.5, 0.5];
+        let term = HomeostaticEngine::diffusion_term(1.0, 0, &[1, 2], &activity);
+        assert!(term.abs() < 1e-9);
+    }
+
+    fn zone_def(id: usize, neighbors: Vec<usize>) -> ZoneManifest {
+        ZoneManifest {
+            id,
+            name: format!("zone-{id}"),
+            initial_activity: 0.5,
+            target: None,
+            neighbors,
+        }
+    }
+
+    #[test]
+    fn from_zone_manifests_rejects_out_of_range_neighbor() {
+        let config = HomeostaticConfig::default();
+        let zone_defs = vec![zone_def(0, vec![5]), zone_def(1, vec![0])];
+
+        let result = HomeostaticEngine::from_zone_manifests(config, &zone_defs);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn neighbor_average_activity_reflects_coupled_zones() {
+        let config = HomeostaticConfig::default();
+        let zone_defs = vec![zone_def(0, vec![1, 2]), zone_def(1, vec![]), zone_def(2, vec![])];
+        let mut engine = HomeostaticEngine::from_zone_manifests(config, &zone_defs).unwrap();
+
+        engine.apply_influence(1, 0.2);
+        engine.apply_influence(2, 0.4);
+
+        let avg = engine.neighbor_average_activity(0).unwrap();
+        assert!((avg - (0.7 + 0.9) / 2.0).abs() < 1e-9);
+        assert!(engine.neighbor_average_activity(1).is_none());
+    }
+}
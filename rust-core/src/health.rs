@@ -0,0 +1,130 @@
+//! Liveness/readiness tracking for the server's background components.
+//!
+//! Each long-running piece (the zone-update tick loop, the Luna engine, the
+//! persistence store, the detection runner) holds a [`HealthState`] handle
+//! and calls [`HealthState::heartbeat`] whenever it makes progress, or
+//! [`HealthState::record_error`] when something goes wrong. `GET
+//! /api/health` aggregates the latest heartbeats into an overall verdict so
+//! the endpoint is usable as a real probe rather than a static `200 OK`.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentStatus {
+    Starting,
+    Running,
+    Stalled,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    pub status: ComponentStatus,
+    pub last_heartbeat_secs_ago: Option<f64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: ComponentStatus,
+    pub components: HashMap<String, ComponentHealth>,
+}
+
+impl HealthReport {
+    /// `false` once any component has failed or stalled past its interval.
+    pub fn is_ready(&self) -> bool {
+        self.status == ComponentStatus::Running || self.status == ComponentStatus::Starting
+    }
+}
+
+#[derive(Debug)]
+struct ComponentRecord {
+    status: ComponentStatus,
+    last_heartbeat: Instant,
+    // A stalled tick loop should only turn the overall verdict unready once
+    // it's missed this many consecutive intervals, so one slow tick doesn't
+    // flap the probe.
+    stall_after: Duration,
+    last_error: Option<String>,
+}
+
+/// Shared handle components heartbeat into, each under the name it was
+/// [`HealthState::register`]ed with.
+#[derive(Default)]
+pub struct HealthState {
+    components: Mutex<HashMap<String, ComponentRecord>>,
+}
+
+impl HealthState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a component as `Starting` if it isn't known yet; later
+    /// heartbeats refer to it by `name`. `stall_after` is how long
+    /// since the last heartbeat before the component counts as `Stalled`.
+    pub fn register(&self, name: &str, stall_after: Duration) {
+        let mut components = self.components.lock().unwrap();
+        components.entry(name.to_string()).or_insert(ComponentRecord {
+            status: ComponentStatus::Starting,
+            last_heartbeat: Instant::now(),
+            stall_after,
+            last_error: None,
+        });
+    }
+
+    /// Marks `name` as having made progress just now.
+    pub fn heartbeat(&self, name: &str) {
+        let mut components = self.components.lock().unwrap();
+        if let Some(component) = components.get_mut(name) {
+            component.status = ComponentStatus::Running;
+            component.last_heartbeat = Instant::now();
+        }
+    }
+
+    /// Records a component failure; it stays `Failed` until its next
+    /// successful heartbeat.
+    pub fn record_error(&self, name: &str, error: impl ToString) {
+        let mut components = self.components.lock().unwrap();
+        if let Some(component) = components.get_mut(name) {
+            component.status = ComponentStatus::Failed;
+            component.last_error = Some(error.to_string());
+        }
+    }
+
+    /// Aggregates every registered component's status: `Failed` if any
+    /// component has failed, `Stalled` if any has missed its heartbeat
+    /// window, `Starting` while any hasn't reported yet, else `Running`.
+    pub fn report(&self) -> HealthReport {
+        let mut components = self.components.lock().unwrap();
+        let mut overall = ComponentStatus::Running;
+        let mut out = HashMap::with_capacity(components.len());
+
+        for (name, component) in components.iter_mut() {
+            let elapsed = component.last_heartbeat.elapsed();
+            if component.status != ComponentStatus::Failed && elapsed > component.stall_after {
+                component.status = ComponentStatus::Stalled;
+            }
+
+            overall = match (overall, component.status) {
+                (_, ComponentStatus::Failed) | (ComponentStatus::Failed, _) => ComponentStatus::Failed,
+                (_, ComponentStatus::Stalled) | (ComponentStatus::Stalled, _) => ComponentStatus::Stalled,
+                (ComponentStatus::Running, ComponentStatus::Starting) => ComponentStatus::Starting,
+                (other, _) => other,
+            };
+
+            out.insert(name.clone(), ComponentHealth {
+                status: component.status,
+                last_heartbeat_secs_ago: Some(elapsed.as_secs_f64()),
+                last_error: component.last_error.clone(),
+            });
+        }
+
+        HealthReport { status: overall, components: out }
+    }
+}
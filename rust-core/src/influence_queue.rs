@@ -0,0 +1,93 @@
+//! Batched, debounced influence application.
+//!
+//! `HomeostaticEngine::apply_influence` used to mutate a zone the instant a
+//! request arrived, so a burst of rapid influences against one zone caused
+//! jitter and could slam it straight to a boundary. Incoming requests are
+//! instead pushed onto a per-zone queue keyed by a scheduled apply-time; a
+//! background task coalesces everything pending for a zone within the
+//! debounce window by summing magnitudes and applies the merged delta once
+//! per tick, so the ordering of concurrent influences is deterministic
+//! within that tick.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::HomeostaticEngine;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InfluenceRequest {
+    pub zone_id: usize,
+    pub influence: f64,
+}
+
+struct PendingInfluence {
+    magnitude: f64,
+    apply_at: Instant,
+}
+
+pub struct InfluenceQueue {
+    debounce: Duration,
+    pending: Mutex<HashMap<usize, VecDeque<PendingInfluence>>>,
+}
+
+impl InfluenceQueue {
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn enqueue(&self, request: InfluenceRequest) {
+        let mut pending = self.pending.lock().unwrap();
+        pending.entry(request.zone_id).or_insert_with(VecDeque::new).push_back(PendingInfluence {
+            magnitude: request.influence,
+            apply_at: Instant::now() + self.debounce,
+        });
+    }
+
+    /// Drains every entry whose apply-time has passed, coalescing each
+    /// zone's backlog into a single summed delta.
+    fn drain_ready(&self) -> Vec<(usize, f64)> {
+        let now = Instant::now();
+        let mut pending = self.pending.lock().unwrap();
+        let mut merged = Vec::new();
+
+        for (&zone_id, queue) in pending.iter_mut() {
+            let mut sum = 0.0;
+            let mut any = false;
+            while matches!(queue.front(), Some(p) if p.apply_at <= now) {
+                sum += queue.pop_front().unwrap().magnitude;
+                any = true;
+            }
+            if any {
+                merged.push((zone_id, sum));
+            }
+        }
+
+        merged
+    }
+}
+
+/// Spawns a background task that, once per `tick`, applies every zone's
+/// coalesced debounced influence to `engine`.
+pub fn spawn_scheduler(engine: Arc<Mutex<HomeostaticEngine>>, queue: Arc<InfluenceQueue>, tick: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(tick);
+        loop {
+            ticker.tick().await;
+            let ready = queue.drain_ready();
+            if ready.is_empty() {
+                continue;
+            }
+
+            let mut engine = engine.lock().unwrap();
+            for (zone_id, delta) in ready {
+                engine.apply_influence(zone_id, delta);
+            }
+        }
+    });
+}
@@ -4,9 +4,15 @@
 //! Enforces bounds and applies homeostatic correction
 
 pub mod engine;
+pub mod influence_queue;
+pub mod manifest;
+pub mod snapshot;
 pub mod zone;
 pub mod api;
 
 pub use engine::HomeostaticEngine;
+pub use influence_queue::{InfluenceQueue, InfluenceRequest};
+pub use manifest::ZoneManifest;
+pub use snapshot::EngineSnapshot;
 pub use zone::Zone;
 pub use api::start_server;
@@ -1,10 +1,21 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use chrono::Timelike;
 use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 use uuid::Uuid;
 
+// rustfft's `Complex` is imported where used, fully-qualified, so it's
+// clear at each call site whether the hand-rolled `fft` below or the real
+// library is in play.
+
+/// Default directory Luna's evolution state is checkpointed to, relative to
+/// the working directory; override with the `LUNA_STATE_DIR` env var.
+pub const DEFAULT_STATE_DIR: &str = "data";
+const STATE_FILE_NAME: &str = "luna_evolution_state.json";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Conversation {
     pub id: String,
@@ -37,22 +48,35 @@ pub struct ZoneContext {
     pub primary_function: String,
 }
 
+// Generalizes what used to be five special-cased `EffectType` labels into a
+// single "change this target parameter" operation: a plant/drug pairing now
+// declares which zone field it nudges, by how much, over what curve.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BioCoreEffect {
     pub plant_name: String,
     pub drug_name: String,
     pub synergy_score: f64,
-    pub effect_type: EffectType,
+    pub parameter: ZoneParameter,
+    pub delta: f64,
     pub duration_minutes: u32,
+    pub curve: EffectCurve,
+}
+
+// The zone-state field a `BioCoreEffect` targets. `Need` carries the need's
+// name since zones can carry an arbitrary set of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ZoneParameter {
+    Activity,
+    Stress,
+    Need(String),
 }
 
+// How `delta` is spread across `duration_minutes` once an effect is applied.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum EffectType {
-    Calming,
-    Activating,
-    Balancing,
-    Purifying,
-    Relaxing,
+pub enum EffectCurve {
+    Instant,
+    Linear,
+    ExponentialDecay,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +102,250 @@ pub enum IntelligenceLevel {
     Autonomous,
 }
 
+// The discretized state a BioCore recommendation is conditioned on: each
+// continuous zone reading is bucketed into `STATE_BINS` bins so the state
+// space stays small enough for a tabular Q-table.
+const STATE_BINS: f64 = 4.0;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ZoneState {
+    activity_bin: u8,
+    stress_bin: u8,
+    density_bin: u8,
+    primary_function: String,
+}
+
+fn bucket(value: f64) -> u8 {
+    ((value.clamp(0.0, 1.0) * STATE_BINS) as u8).min(STATE_BINS as u8 - 1)
+}
+
+impl ZoneState {
+    fn from_context(zone: &ZoneContext) -> Self {
+        Self {
+            activity_bin: bucket(zone.activity_level),
+            stress_bin: bucket(zone.stress_level),
+            density_bin: bucket(zone.population_density),
+            primary_function: zone.primary_function.clone(),
+        }
+    }
+}
+
+// Identifies a `BioCoreEffect` by the plant+drug pairing that names it,
+// since the rest of the effect (delta, curve, ...) is just that pairing's
+// fixed template from `biocore_catalog`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BioCoreAction {
+    pub plant_name: String,
+    pub drug_name: String,
+}
+
+impl BioCoreAction {
+    fn from_effect(effect: &BioCoreEffect) -> Self {
+        Self { plant_name: effect.plant_name.clone(), drug_name: effect.drug_name.clone() }
+    }
+}
+
+// Q-values for each (state, action) pair the policy has tried, per the
+// tabular MDP described on `LunaEvolutionEngine::recommend_biocore`.
+type StateEstimates = HashMap<ZoneState, HashMap<BioCoreAction, f64>>;
+
+// Unlike `ZoneState`/`BioCoreAction` above, the optimization-frequency
+// policy isn't tabular: the state space (continuous zone readings) is
+// small-dimensional and dense enough that a linear Q(s,a) = w_a . f(s)
+// generalizes across zones instead of needing a bucketed table per zone.
+const FREQUENCY_FEATURE_COUNT: usize = 5;
+
+fn frequency_state_features(zone: &ZoneContext, efficiency: f64) -> [f64; FREQUENCY_FEATURE_COUNT] {
+    [zone.activity_level, zone.stress_level, zone.population_density, efficiency / 100.0, 1.0]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum FrequencyAction {
+    Decrease,
+    Hold,
+    Increase,
+}
+
+impl FrequencyAction {
+    const ALL: [FrequencyAction; 3] = [Self::Decrease, Self::Hold, Self::Increase];
+
+    fn multiplier(self) -> f64 {
+        match self {
+            Self::Decrease => 0.8,
+            Self::Hold => 1.0,
+            Self::Increase => 1.25,
+        }
+    }
+}
+
+// The (state, action, resulting frequency, efficiency) a zone is waiting
+// to be scored on: the reward for picking `action` isn't known until the
+// zone's *next* conversation comes in, so this sits in `pending` until
+// then, the same way a real step-by-step RL loop would bootstrap off the
+// following observation instead of the current one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingFrequencyStep {
+    features: [f64; FREQUENCY_FEATURE_COUNT],
+    action: FrequencyAction,
+    frequency: f64,
+    efficiency: f64,
+}
+
+// Learned replacement for the old hardcoded `base_frequency *
+// stress_multiplier * activity_multiplier` formula in
+// `calculate_optimization_frequency`. Persisted alongside `learning_cache`
+// so the policy keeps improving across restarts instead of resetting to
+// `DEFAULT_FREQUENCY` every time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrequencyOptimizer {
+    decrease_weights: [f64; FREQUENCY_FEATURE_COUNT],
+    hold_weights: [f64; FREQUENCY_FEATURE_COUNT],
+    increase_weights: [f64; FREQUENCY_FEATURE_COUNT],
+    zone_frequency: HashMap<String, f64>,
+    pending: HashMap<String, PendingFrequencyStep>,
+}
+
+impl Default for FrequencyOptimizer {
+    fn default() -> Self {
+        Self {
+            decrease_weights: [0.0; FREQUENCY_FEATURE_COUNT],
+            hold_weights: [0.0; FREQUENCY_FEATURE_COUNT],
+            increase_weights: [0.0; FREQUENCY_FEATURE_COUNT],
+            zone_frequency: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+}
+
+impl FrequencyOptimizer {
+    // Exploration rate for the epsilon-greedy action choice; unlike
+    // `recommend_biocore`'s epsilon this doesn't decay, since the
+    // optimizer keeps needing to re-explore as zone conditions drift.
+    const EPSILON: f64 = 0.1;
+    const MIN_FREQUENCY: f64 = 0.1;
+    const MAX_FREQUENCY: f64 = 10.0;
+    // What `calculate_optimization_frequency` used to hardcode as
+    // `base_frequency`; now just the starting point the policy adjusts
+    // away from.
+    const DEFAULT_FREQUENCY: f64 = 2.0;
+
+    fn weights(&self, action: FrequencyAction) -> &[f64; FREQUENCY_FEATURE_COUNT] {
+        match action {
+            FrequencyAction::Decrease => &self.decrease_weights,
+            FrequencyAction::Hold => &self.hold_weights,
+            FrequencyAction::Increase => &self.increase_weights,
+        }
+    }
+
+    fn weights_mut(&mut self, action: FrequencyAction) -> &mut [f64; FREQUENCY_FEATURE_COUNT] {
+        match action {
+            FrequencyAction::Decrease => &mut self.decrease_weights,
+            FrequencyAction::Hold => &mut self.hold_weights,
+            FrequencyAction::Increase => &mut self.increase_weights,
+        }
+    }
+
+    fn q_value(&self, action: FrequencyAction, features: &[f64; FREQUENCY_FEATURE_COUNT]) -> f64 {
+        self.weights(action).iter().zip(features).map(|(w, f)| w * f).sum()
+    }
+
+    fn max_q(&self, features: &[f64; FREQUENCY_FEATURE_COUNT]) -> f64 {
+        FrequencyAction::ALL.iter().map(|&a| self.q_value(a, features)).fold(f64::MIN, f64::max)
+    }
+
+    fn choose_action(&self, features: &[f64; FREQUENCY_FEATURE_COUNT]) -> FrequencyAction {
+        if rand::random::<f64>() < Self::EPSILON {
+            let index = (rand::random::<f64>() * FrequencyAction::ALL.len() as f64) as usize
+                % FrequencyAction::ALL.len();
+            return FrequencyAction::ALL[index];
+        }
+
+        FrequencyAction::ALL
+            .iter()
+            .copied()
+            .max_by(|&a, &b| self.q_value(a, features).partial_cmp(&self.q_value(b, features)).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap_or(FrequencyAction::Hold)
+    }
+
+    fn frequency_for(&self, zone_name: &str) -> f64 {
+        self.zone_frequency.get(zone_name).copied().unwrap_or(Self::DEFAULT_FREQUENCY)
+    }
+
+    // Completes the step pending from this zone's last conversation (if
+    // any) with a TD update bootstrapped off `features`/`reward`, then
+    // picks and applies the next action for *this* conversation, leaving
+    // it pending for the one after. Returns the resulting learned
+    // frequency, i.e. what `calculate_optimization_frequency` now exposes.
+    fn step(&mut self, zone_name: &str, features: [f64; FREQUENCY_FEATURE_COUNT], reward: f64, alpha: f64) -> f64 {
+        if let Some(prev) = self.pending.remove(zone_name) {
+            let max_next_q = self.max_q(&features);
+            let current_q = self.q_value(prev.action, &prev.features);
+            let td_target = reward + LunaEvolutionEngine::Q_GAMMA * max_next_q;
+            let td_error = alpha * (td_target - current_q);
+            for (w, f) in self.weights_mut(prev.action).iter_mut().zip(prev.features) {
+                *w += td_error * f;
+            }
+        }
+
+        let previous_frequency = self.frequency_for(zone_name);
+        let action = self.choose_action(&features);
+        let frequency = (previous_frequency * action.multiplier()).clamp(Self::MIN_FREQUENCY, Self::MAX_FREQUENCY);
+        self.zone_frequency.insert(zone_name.to_string(), frequency);
+
+        let efficiency = features[3] * 100.0;
+        self.pending.insert(
+            zone_name.to_string(),
+            PendingFrequencyStep { features, action, frequency, efficiency },
+        );
+
+        frequency
+    }
+}
+
+// The fixed catalog of BioCore interventions `recommend_biocore` chooses
+// between — the same plant/drug pairings `fast_api_server::suggestion_for_need`
+// offers, expressed as ready-to-apply effect templates.
+fn biocore_catalog() -> Vec<BioCoreEffect> {
+    vec![
+        BioCoreEffect {
+            plant_name: "Ashwagandha".to_string(),
+            drug_name: "DrugA".to_string(),
+            synergy_score: 0.85,
+            parameter: ZoneParameter::Stress,
+            delta: -0.3,
+            duration_minutes: 30,
+            curve: EffectCurve::Linear,
+        },
+        BioCoreEffect {
+            plant_name: "Turmeric".to_string(),
+            drug_name: "DrugB".to_string(),
+            synergy_score: 0.90,
+            parameter: ZoneParameter::Stress,
+            delta: -0.4,
+            duration_minutes: 20,
+            curve: EffectCurve::ExponentialDecay,
+        },
+        BioCoreEffect {
+            plant_name: "Ginseng".to_string(),
+            drug_name: "DrugC".to_string(),
+            synergy_score: 0.75,
+            parameter: ZoneParameter::Activity,
+            delta: 0.25,
+            duration_minutes: 30,
+            curve: EffectCurve::Linear,
+        },
+        BioCoreEffect {
+            plant_name: "Basil".to_string(),
+            drug_name: "DrugD".to_string(),
+            synergy_score: 0.65,
+            parameter: ZoneParameter::Activity,
+            delta: 0.1,
+            duration_minutes: 15,
+            curve: EffectCurve::ExponentialDecay,
+        },
+    ]
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvolutionMetrics {
     pub conversations_processed: u64,
@@ -89,18 +357,1079 @@ pub struct EvolutionMetrics {
     pub next_evolution_threshold: u64,
 }
 
-pub struct LunaEvolutionEngine {
-    personality: Arc<Mutex<LunaPersonality>>,
-    conversation_history: Arc<Mutex<Vec<Conversation>>>,
-    zone_patterns: Arc<Mutex<HashMap<String, Vec<f64>>>>,
-    biocore_effectiveness: Arc<Mutex<HashMap<String, f64>>>,
-    evolution_metrics: Arc<Mutex<EvolutionMetrics>>,
-    learning_cache: Arc<Mutex<HashMap<String, f64>>>,
+/// A flagged abnormal reading in a zone's activity series, produced by one
+/// of the [`AnomalyDetector`] implementations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZoneAnomaly {
+    pub zone_name: String,
+    pub detector: String,
+    pub severity: f64,
+    pub value: f64,
+}
+
+/// Flags abnormal states in a zone's activity series. Implementations keep
+/// whatever model they need (running statistics, learned templates...) and
+/// are stateless across calls — all the history they need is passed in via
+/// `samples`.
+pub trait AnomalyDetector: Send + Sync {
+    fn detector_type(&self) -> &'static str;
+    fn detect(&self, zone_name: &str, samples: &[f64]) -> Option<ZoneAnomaly>;
+}
+
+fn std_dev(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    (values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / values.len() as f64).sqrt()
+}
+
+// Slope of the ordinary-least-squares fit of `values` against their index.
+fn linear_trend(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+
+    let n = values.len() as f64;
+    let sum_x: f64 = (0..values.len()).map(|i| i as f64).sum();
+    let sum_y: f64 = values.iter().sum();
+    let sum_xy: f64 = values.iter().enumerate().map(|(i, y)| i as f64 * y).sum();
+    let sum_x2: f64 = (0..values.len()).map(|i| (i as f64).powi(2)).sum();
+
+    (n * sum_xy - sum_x * sum_y) / (n * sum_x2 - sum_x.powi(2))
+}
+
+// Pearson correlation between `a` and `b` over their overlapping length
+// (the most recent samples of whichever is longer), after normalizing
+// both to zero-mean/unit-variance. 0.0 when there isn't enough overlap or
+// either series is constant (undefined correlation).
+fn pearson_correlation(a: &[f64], b: &[f64]) -> f64 {
+    let len = a.len().min(b.len());
+    if len < 2 {
+        return 0.0;
+    }
+    let a = &a[a.len() - len..];
+    let b = &b[b.len() - len..];
+
+    let mean_a = a.iter().sum::<f64>() / len as f64;
+    let mean_b = b.iter().sum::<f64>() / len as f64;
+    let std_a = (a.iter().map(|x| (x - mean_a).powi(2)).sum::<f64>() / len as f64).sqrt();
+    let std_b = (b.iter().map(|x| (x - mean_b).powi(2)).sum::<f64>() / len as f64).sqrt();
+    if std_a < f64::EPSILON || std_b < f64::EPSILON {
+        return 0.0;
+    }
+
+    let dot: f64 = a.iter().zip(b).map(|(x, y)| ((x - mean_a) / std_a) * ((y - mean_b) / std_b)).sum();
+    (dot / len as f64).clamp(-1.0, 1.0)
+}
+
+/// Flags any sample more than `k` standard deviations from the zone's
+/// rolling mean.
+pub struct ThresholdDetector {
+    pub k: f64,
+}
+
+impl Default for ThresholdDetector {
+    fn default() -> Self {
+        Self { k: 3.0 }
+    }
+}
+
+impl AnomalyDetector for ThresholdDetector {
+    fn detector_type(&self) -> &'static str {
+        "threshold"
+    }
+
+    fn detect(&self, zone_name: &str, samples: &[f64]) -> Option<ZoneAnomaly> {
+        if samples.len() < 2 {
+            return None;
+        }
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let sigma = std_dev(samples);
+        if sigma <= f64::EPSILON {
+            return None;
+        }
+
+        let latest = *samples.last().unwrap();
+        let z = (latest - mean).abs() / sigma;
+        if z > self.k {
+            Some(ZoneAnomaly {
+                zone_name: zone_name.to_string(),
+                detector: self.detector_type().to_string(),
+                severity: z,
+                value: latest,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+// Normalized cross-correlation (Pearson's r) between two equal-length
+// windows: 1.0 for identical shapes, 0.0 for uncorrelated ones.
+fn normalized_cross_correlation(a: &[f64], b: &[f64]) -> f64 {
+    let mean_a = a.iter().sum::<f64>() / a.len() as f64;
+    let mean_b = b.iter().sum::<f64>() / b.len() as f64;
+
+    let numerator: f64 = a.iter().zip(b).map(|(x, y)| (x - mean_a) * (y - mean_b)).sum();
+    let denom_a = a.iter().map(|x| (x - mean_a).powi(2)).sum::<f64>().sqrt();
+    let denom_b = b.iter().map(|y| (y - mean_b).powi(2)).sum::<f64>().sqrt();
+    if denom_a <= f64::EPSILON || denom_b <= f64::EPSILON {
+        return 0.0;
+    }
+
+    numerator / (denom_a * denom_b)
+}
+
+/// Matches the tail of a zone's activity series against learned
+/// "anti-pattern" templates — short activity shapes known to precede a
+/// critical stress spike — via normalized cross-correlation.
+pub struct PatternDetector {
+    pub templates: Vec<Vec<f64>>,
+    pub confidence: f64,
+}
+
+impl Default for PatternDetector {
+    fn default() -> Self {
+        Self {
+            // A steady climb into a sharp spike: the shape Luna has learned
+            // tends to precede a zone tipping into critical stress.
+            templates: vec![vec![0.2, 0.35, 0.5, 0.7, 0.95]],
+            confidence: 0.85,
+        }
+    }
+}
+
+impl AnomalyDetector for PatternDetector {
+    fn detector_type(&self) -> &'static str {
+        "pattern"
+    }
+
+    fn detect(&self, zone_name: &str, samples: &[f64]) -> Option<ZoneAnomaly> {
+        self.templates
+            .iter()
+            .filter(|template| samples.len() >= template.len())
+            .filter_map(|template| {
+                let window = &samples[samples.len() - template.len()..];
+                let correlation = normalized_cross_correlation(window, template);
+                (correlation > self.confidence).then(|| ZoneAnomaly {
+                    zone_name: zone_name.to_string(),
+                    detector: self.detector_type().to_string(),
+                    severity: correlation,
+                    value: *window.last().unwrap(),
+                })
+            })
+            .next()
+    }
+}
+
+// The strongest non-DC frequency found by `dominant_frequency`, plus enough
+// of its FFT context (`window_len`, `fft_size`) to extrapolate the
+// reconstructed sinusoid forward.
+#[derive(Debug, Clone, Copy)]
+struct SpectralPeak {
+    bin: usize,
+    magnitude: f64,
+    phase: f64,
+    period: f64,
+    relative_power: f64,
+    window_len: usize,
+    fft_size: usize,
+}
+
+// Smallest power of two covering `n`, capped at 64 so the FFT stays cheap.
+fn fft_size_for(n: usize) -> usize {
+    let mut size = 1;
+    while size < n && size < 64 {
+        size *= 2;
+    }
+    size.max(1)
+}
+
+// In-place-equivalent radix-2 Cooley-Tukey FFT over (re, im) pairs.
+// `input.len()` must be a power of two.
+fn fft(input: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let n = input.len();
+    if n <= 1 {
+        return input.to_vec();
+    }
+
+    let even: Vec<(f64, f64)> = input.iter().copied().step_by(2).collect();
+    let odd: Vec<(f64, f64)> = input.iter().copied().skip(1).step_by(2).collect();
+    let even_fft = fft(&even);
+    let odd_fft = fft(&odd);
+
+    let mut out = vec![(0.0, 0.0); n];
+    for k in 0..n / 2 {
+        let angle = -2.0 * std::f64::consts::PI * k as f64 / n as f64;
+        let (tw_re, tw_im) = (angle.cos(), angle.sin());
+        let (or, oi) = odd_fft[k];
+        let t = (tw_re * or - tw_im * oi, tw_re * oi + tw_im * or);
+        let (er, ei) = even_fft[k];
+        out[k] = (er + t.0, ei + t.1);
+        out[k + n / 2] = (er - t.0, ei - t.1);
+    }
+    out
+}
+
+// Finds the strongest non-DC frequency bin in the latest (up to 64) samples
+// of `samples`, after mean-subtracting and zero-padding to the next power
+// of two. `None` if there isn't enough data for a meaningful FFT.
+fn dominant_frequency(samples: &[f64]) -> Option<SpectralPeak> {
+    if samples.len() < 4 {
+        return None;
+    }
+
+    let window = &samples[samples.len().saturating_sub(64)..];
+    let mean = window.iter().sum::<f64>() / window.len() as f64;
+    let size = fft_size_for(window.len());
+
+    let mut padded: Vec<(f64, f64)> = window.iter().map(|x| (x - mean, 0.0)).collect();
+    padded.resize(size, (0.0, 0.0));
+
+    let spectrum = fft(&padded);
+    let half = size / 2;
+    if half < 2 {
+        return None;
+    }
+
+    let magnitudes: Vec<f64> = spectrum[..half].iter().map(|(re, im)| (re * re + im * im).sqrt()).collect();
+    let total_energy: f64 = magnitudes[1..].iter().map(|m| m * m).sum();
+    if total_energy <= f64::EPSILON {
+        return None;
+    }
+
+    let (bin, magnitude) = magnitudes[1..]
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, m)| (i + 1, *m))?;
+
+    let (re, im) = spectrum[bin];
+    Some(SpectralPeak {
+        bin,
+        magnitude,
+        phase: im.atan2(re),
+        period: size as f64 / bin as f64,
+        relative_power: (magnitude * magnitude) / total_energy,
+        window_len: window.len(),
+        fft_size: size,
+    })
+}
+
+// Fixed-size window `spectral_features` runs an FFT over: long enough to
+// catch a daily activity cycle, short enough to keep nearest-template
+// lookups cheap. Must stay a power of two for `rustfft`.
+const TEMPLATE_WINDOW_SIZE: usize = 64;
+// How many low-frequency bins (beyond DC) get folded into the feature
+// vector; this is where a recurring cycle shows up.
+const TEMPLATE_SPECTRAL_BINS: usize = 16;
+// mean, variance, min, max of the raw series, plus (magnitude, phase) per
+// retained bin.
+const TEMPLATE_FEATURE_COUNT: usize = 4 + TEMPLATE_SPECTRAL_BINS * 2;
+
+// Pads (by repeating the earliest sample) or truncates `samples` to
+// exactly `size` entries, keeping the most recent `size` samples.
+fn fixed_window(samples: &[f64], size: usize) -> Vec<f64> {
+    if samples.len() >= size {
+        return samples[samples.len() - size..].to_vec();
+    }
+    let first = samples.first().copied().unwrap_or(0.0);
+    let mut window = vec![first; size - samples.len()];
+    window.extend_from_slice(samples);
+    window
+}
+
+// A labelled training example for nearest-template prediction: the
+// spectral fingerprint of a `TEMPLATE_WINDOW_SIZE`-sample window, and what
+// the zone's activity/stress actually did right after that window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PatternTemplate {
+    // L2-normalized, so templates can be compared by plain Euclidean
+    // distance regardless of each feature's raw scale.
+    features: Vec<f64>,
+    next_activity: f64,
+    next_stress: f64,
+    // The raw (un-transformed) window the fingerprint above was taken
+    // from, kept alongside it so `correlation_score` can Pearson-correlate
+    // directly in the time domain instead of in spectral-feature space.
+    // Absent on templates recorded before this field existed.
+    #[serde(default)]
+    raw_window: Vec<f64>,
+}
+
+// Caps the replay buffer of labelled templates kept per zone, evicting the
+// oldest the same way `zone_patterns` caps its own history.
+const MAX_TEMPLATES_PER_ZONE: usize = 20;
+// Below this much history, a window is mostly padding - not worth turning
+// into a template.
+const MIN_TEMPLATE_HISTORY: usize = 8;
+
+fn normalize_features(features: &[f64]) -> Vec<f64> {
+    let norm = features.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if norm <= f64::EPSILON {
+        return features.to_vec();
+    }
+    features.iter().map(|x| x / norm).collect()
+}
+
+fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt()
+}
+
+// Builds the real-FFT-backed feature vector `PatternTemplate`s are matched
+// on: mean/variance/min/max of the raw window plus the magnitude and
+// phase of its first `TEMPLATE_SPECTRAL_BINS` frequency bins (where a
+// daily activity cycle shows up), via `rustfft` rather than the
+// hand-rolled radix-2 transform `dominant_frequency` uses.
+fn spectral_features(samples: &[f64]) -> Vec<f64> {
+    let window = fixed_window(samples, TEMPLATE_WINDOW_SIZE);
+    let mean = window.iter().sum::<f64>() / window.len() as f64;
+    let variance = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / window.len() as f64;
+    let min = window.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = window.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+    let mut buffer: Vec<rustfft::num_complex::Complex<f64>> =
+        window.iter().map(|&x| rustfft::num_complex::Complex::new(x - mean, 0.0)).collect();
+    let mut planner = rustfft::FftPlanner::new();
+    planner.plan_fft_forward(buffer.len()).process(&mut buffer);
+
+    let mut features = Vec::with_capacity(TEMPLATE_FEATURE_COUNT);
+    features.extend([mean, variance, min, max]);
+    for bin in 1..=TEMPLATE_SPECTRAL_BINS {
+        let c = buffer.get(bin).copied().unwrap_or_default();
+        features.push(c.norm());
+        features.push(c.arg());
+    }
+    features
+}
+
+// Number of columns in the feature vector `effectiveness_features` builds.
+// [activity_level, stress_level, population_density, synergy_score,
+//  hour_of_day (0..1), recent activity trend, recent activity variance,
+//  parameter one-hot (activity/stress/need), curve one-hot
+//  (instant/linear/exponential_decay)]
+const EFFECTIVENESS_FEATURE_COUNT: usize = 13;
+
+// Builds the feature row `EffectivenessModel` trains and predicts on for a
+// single (zone, candidate effect) pairing at `timestamp`. `trend` and
+// `variance` come from the zone's recent activity series.
+fn effectiveness_features(
+    zone: &ZoneContext,
+    effect: &BioCoreEffect,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    trend: f64,
+    variance: f64,
+) -> [f64; EFFECTIVENESS_FEATURE_COUNT] {
+    let (param_activity, param_stress, param_need) = match &effect.parameter {
+        ZoneParameter::Activity => (1.0, 0.0, 0.0),
+        ZoneParameter::Stress => (0.0, 1.0, 0.0),
+        ZoneParameter::Need(_) => (0.0, 0.0, 1.0),
+    };
+    let (curve_instant, curve_linear, curve_expdecay) = match &effect.curve {
+        EffectCurve::Instant => (1.0, 0.0, 0.0),
+        EffectCurve::Linear => (0.0, 1.0, 0.0),
+        EffectCurve::ExponentialDecay => (0.0, 0.0, 1.0),
+    };
+
+    [
+        zone.activity_level,
+        zone.stress_level,
+        zone.population_density,
+        effect.synergy_score,
+        timestamp.hour() as f64 / 24.0,
+        trend,
+        variance,
+        param_activity,
+        param_stress,
+        param_need,
+        curve_instant,
+        curve_linear,
+        curve_expdecay,
+    ]
+}
+
+// A depth-1 regression tree (decision stump): splits on a single feature
+// and predicts the mean residual of whichever side a row falls on. Each
+// boosting round in `EffectivenessModel` fits one of these to the current
+// residuals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RegressionStump {
+    feature: usize,
+    threshold: f64,
+    left_value: f64,
+    right_value: f64,
+}
+
+impl RegressionStump {
+    fn predict(&self, features: &[f64]) -> f64 {
+        if features[self.feature] <= self.threshold {
+            self.left_value
+        } else {
+            self.right_value
+        }
+    }
+
+    // Greedily picks the (feature, threshold) split minimizing the sum of
+    // squared residuals, trying every observed value of every feature as a
+    // candidate threshold.
+    fn fit(rows: &[[f64; EFFECTIVENESS_FEATURE_COUNT]], residuals: &[f64]) -> Self {
+        let mut best = Self { feature: 0, threshold: 0.0, left_value: 0.0, right_value: 0.0 };
+        let mut best_sse = f64::INFINITY;
+
+        for feature in 0..EFFECTIVENESS_FEATURE_COUNT {
+            let mut thresholds: Vec<f64> = rows.iter().map(|r| r[feature]).collect();
+            thresholds.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            thresholds.dedup();
+
+            for &threshold in &thresholds {
+                let (mut left_sum, mut left_n, mut right_sum, mut right_n) = (0.0, 0usize, 0.0, 0usize);
+                for (row, &residual) in rows.iter().zip(residuals) {
+                    if row[feature] <= threshold {
+                        left_sum += residual;
+                        left_n += 1;
+                    } else {
+                        right_sum += residual;
+                        right_n += 1;
+                    }
+                }
+                if left_n == 0 || right_n == 0 {
+                    continue;
+                }
+
+                let left_value = left_sum / left_n as f64;
+                let right_value = right_sum / right_n as f64;
+                let sse: f64 = rows
+                    .iter()
+                    .zip(residuals)
+                    .map(|(row, &residual)| {
+                        let prediction = if row[feature] <= threshold { left_value } else { right_value };
+                        (residual - prediction).powi(2)
+                    })
+                    .sum();
+
+                if sse < best_sse {
+                    best_sse = sse;
+                    best = Self { feature, threshold, left_value, right_value };
+                }
+            }
+        }
+
+        best
+    }
+}
+
+/// Gradient-boosted regressor predicting a `BioCoreEffect`'s effectiveness
+/// for a zone from [`effectiveness_features`], replacing
+/// `calculate_response_effectiveness`'s text-keyword heuristic once enough
+/// labeled conversations have accumulated. Boosts a sequence of
+/// [`RegressionStump`]s against the squared-error residual, shrunk by
+/// `LEARNING_RATE` the way a real GBDT library (XGBoost, LightGBM) would.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectivenessModel {
+    base_value: f64,
+    stumps: Vec<RegressionStump>,
+    trained_on: usize,
+}
+
+impl EffectivenessModel {
+    const LEARNING_RATE: f64 = 0.1;
+    const ROUNDS: usize = 25;
+    /// Below this many labeled conversations, callers fall back to the
+    /// text-keyword heuristic rather than trust the model.
+    pub const MIN_LABELED_CONVERSATIONS: usize = 20;
+
+    // Fits a fresh model over every conversation with both a zone context
+    // and an applied BioCore effect, targeting the realized
+    // `effectiveness_score`. `None` if there isn't yet enough labeled data.
+    //
+    // The activity trend/variance features are computed from the zone's
+    // *current* pattern history rather than what it was at the time of each
+    // historical conversation (which isn't retained per-conversation) - a
+    // simplification shared with `calculate_predictive_accuracy` elsewhere
+    // in this file.
+    fn fit(conversations: &[Conversation], zone_patterns: &HashMap<String, Vec<f64>>) -> Option<Self> {
+        let rows: Vec<([f64; EFFECTIVENESS_FEATURE_COUNT], f64)> = conversations
+            .iter()
+            .filter_map(|c| {
+                let zone = c.zone_context.as_ref()?;
+                let effect = c.biocore_applied.as_ref()?;
+                let samples = zone_patterns.get(&zone.zone_name).map(|v| v.as_slice()).unwrap_or(&[]);
+                let trend = linear_trend(samples);
+                let variance = std_dev(samples);
+                let features = effectiveness_features(zone, effect, c.timestamp, trend, variance);
+                Some((features, c.effectiveness_score))
+            })
+            .collect();
+
+        if rows.len() < Self::MIN_LABELED_CONVERSATIONS {
+            return None;
+        }
+
+        let features: Vec<[f64; EFFECTIVENESS_FEATURE_COUNT]> = rows.iter().map(|(f, _)| *f).collect();
+        let targets: Vec<f64> = rows.iter().map(|(_, t)| *t).collect();
+        let base_value = targets.iter().sum::<f64>() / targets.len() as f64;
+
+        let mut predictions = vec![base_value; targets.len()];
+        let mut stumps = Vec::with_capacity(Self::ROUNDS);
+        for _ in 0..Self::ROUNDS {
+            let residuals: Vec<f64> =
+                targets.iter().zip(&predictions).map(|(target, prediction)| target - prediction).collect();
+            let stump = RegressionStump::fit(&features, &residuals);
+            for (prediction, row) in predictions.iter_mut().zip(&features) {
+                *prediction += Self::LEARNING_RATE * stump.predict(row);
+            }
+            stumps.push(stump);
+        }
+
+        Some(Self { base_value, stumps, trained_on: targets.len() })
+    }
+
+    fn predict(&self, features: &[f64; EFFECTIVENESS_FEATURE_COUNT]) -> f64 {
+        let raw = self.base_value
+            + self.stumps.iter().map(|s| Self::LEARNING_RATE * s.predict(features)).sum::<f64>();
+        raw.clamp(0.0, 1.0)
+    }
+}
+
+// A genome is the ~12 constants `calculate_learning_weight` and
+// `update_personality` used to hardcode: one weight per `InteractionType`
+// (in enum declaration order) followed by one trait growth coefficient
+// per trait, in the order `update_personality` applies them (learning
+// rate, adaptation speed, confidence, memory retention, pattern
+// recognition, strategic thinking).
+const GENOME_WEIGHT_GENES: usize = 6;
+const GENOME_GROWTH_GENES: usize = 6;
+const GENOME_LEN: usize = GENOME_WEIGHT_GENES + GENOME_GROWTH_GENES;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Genome {
+    genes: [f64; GENOME_LEN],
+    // Mean `response_effectiveness` observed over this genome's last
+    // completed trial window; `None` until it has been evaluated at least
+    // once.
+    fitness: Option<f64>,
+}
+
+impl Genome {
+    fn baseline() -> Self {
+        Self {
+            genes: [
+                0.8, 0.9, 1.0, 0.95, 0.5, 1.0, // InteractionType weights
+                0.01, 0.005, 0.02, 0.01, 0.02, 0.015, // trait growth rates
+            ],
+            fitness: None,
+        }
+    }
+
+    fn interaction_weight(&self, interaction_type: &InteractionType) -> f64 {
+        let index = match interaction_type {
+            InteractionType::ZoneAnalysis => 0,
+            InteractionType::BioCoreRecommendation => 1,
+            InteractionType::SystemOptimization => 2,
+            InteractionType::StrategicPlanning => 3,
+            InteractionType::GeneralInquiry => 4,
+            InteractionType::EmergencyResponse => 5,
+        };
+        self.genes[index]
+    }
+
+    fn growth_rates(&self) -> [f64; GENOME_GROWTH_GENES] {
+        self.genes[GENOME_WEIGHT_GENES..].try_into().unwrap()
+    }
+
+    // Keeps the InteractionType weight block from drifting so one type
+    // dominates every genome after enough mutation; the growth-rate block
+    // is left alone since those aren't a relative weighting.
+    fn normalize_weights(&mut self) {
+        let norm = self.genes[..GENOME_WEIGHT_GENES].iter().map(|g| g * g).sum::<f64>().sqrt();
+        if norm > f64::EPSILON {
+            for gene in &mut self.genes[..GENOME_WEIGHT_GENES] {
+                *gene = (*gene / norm) * (GENOME_WEIGHT_GENES as f64).sqrt();
+            }
+        }
+    }
+}
+
+// Box-Muller transform, since `rand::random` (used elsewhere in this file
+// for epsilon-greedy exploration) only gives a uniform draw.
+fn gaussian_delta(std_dev: f64) -> f64 {
+    let u1: f64 = rand::random::<f64>().max(f64::EPSILON);
+    let u2: f64 = rand::random::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos() * std_dev
+}
+
+// Background evolutionary tuner for the genome above. Runs one genome at
+// a time over a trial window of conversations, scores it by the mean
+// `response_effectiveness` recorded in `learning_cache` while it was
+// active, and once every genome in the population has a fitness score,
+// breeds the next generation via tournament selection, crossover biased
+// toward the fitter parent, and Gaussian mutation. The best genome found
+// each generation is promoted to `live`, which is what
+// `calculate_learning_weight` and `update_personality` actually read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvolutionaryTuner {
+    population: Vec<Genome>,
+    active: usize,
+    trial_effectiveness_sum: f64,
+    trial_count: u64,
+    live: Genome,
+}
+
+impl Default for EvolutionaryTuner {
+    fn default() -> Self {
+        Self {
+            population: (0..Self::POPULATION_SIZE).map(|_| Genome::baseline()).collect(),
+            active: 0,
+            trial_effectiveness_sum: 0.0,
+            trial_count: 0,
+            live: Genome::baseline(),
+        }
+    }
+}
+
+impl EvolutionaryTuner {
+    const POPULATION_SIZE: usize = 8;
+    const TRIAL_WINDOW: u64 = 20;
+    const TOURNAMENT_SIZE: usize = 3;
+    const MUTATION_STD_DEV: f64 = 0.05;
+    // Fraction of genes a crossover child takes from its fitter parent.
+    const CROSSOVER_BIAS: f64 = 0.7;
+
+    fn live_genome(&self) -> &Genome {
+        &self.live
+    }
+
+    /// Feeds one more observed `response_effectiveness` sample into the
+    /// genome currently under evaluation, advancing the trial window (and
+    /// breeding the next generation, every `POPULATION_SIZE` trials) as
+    /// it fills up.
+    fn record(&mut self, effectiveness: f64) {
+        self.trial_effectiveness_sum += effectiveness;
+        self.trial_count += 1;
+        if self.trial_count < Self::TRIAL_WINDOW {
+            return;
+        }
+
+        self.population[self.active].fitness = Some(self.trial_effectiveness_sum / self.trial_count as f64);
+        self.trial_effectiveness_sum = 0.0;
+        self.trial_count = 0;
+        self.active += 1;
+
+        if self.active < self.population.len() {
+            return;
+        }
+        self.active = 0;
+        self.promote_and_breed();
+    }
+
+    fn promote_and_breed(&mut self) {
+        if let Some(best) = self.population.iter().max_by(|a, b| {
+            a.fitness.unwrap_or(0.0).partial_cmp(&b.fitness.unwrap_or(0.0)).unwrap_or(std::cmp::Ordering::Equal)
+        }) {
+            self.live = best.clone();
+        }
+
+        let next_generation = (0..self.population.len()).map(|_| self.breed_child()).collect();
+        self.population = next_generation;
+    }
+
+    fn tournament_select(&self) -> &Genome {
+        (0..Self::TOURNAMENT_SIZE)
+            .map(|_| &self.population[(rand::random::<f64>() * self.population.len() as f64) as usize % self.population.len()])
+            .max_by(|a, b| a.fitness.unwrap_or(0.0).partial_cmp(&b.fitness.unwrap_or(0.0)).unwrap_or(std::cmp::Ordering::Equal))
+            .expect("population is never empty")
+    }
+
+    fn breed_child(&self) -> Genome {
+        let parent_a = self.tournament_select();
+        let parent_b = self.tournament_select();
+        let (fitter, other) = if parent_a.fitness.unwrap_or(0.0) >= parent_b.fitness.unwrap_or(0.0) {
+            (parent_a, parent_b)
+        } else {
+            (parent_b, parent_a)
+        };
+
+        let mut genes = [0.0; GENOME_LEN];
+        for (i, gene) in genes.iter_mut().enumerate() {
+            *gene = if rand::random::<f64>() < Self::CROSSOVER_BIAS { fitter.genes[i] } else { other.genes[i] };
+        }
+
+        let mutated_index = (rand::random::<f64>() * GENOME_LEN as f64) as usize % GENOME_LEN;
+        genes[mutated_index] += gaussian_delta(Self::MUTATION_STD_DEV);
+
+        let mut child = Genome { genes, fitness: None };
+        child.normalize_weights();
+        child
+    }
+}
+
+// One zone's recorded effectiveness observation, tracked with an
+// FSRS-style stability instead of a fixed-size ring buffer slot. Ages out
+// by retrievability (`retention::retrievability`) rather than by
+// position, so a zone that hasn't been visited in a while naturally
+// fades instead of being evicted purely on count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MemoryItem {
+    value: f64,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    stability: f64,
+}
+
+// Fixed FSRS-style parameters for the stability update below. A real FSRS
+// scheduler fits `w` per-user from review logs; LUNA has no such corpus to
+// fit against, so these stay constant rather than being exposed as config
+// that nothing would ever tune.
+const FSRS_DIFFICULTY: f64 = 5.0;
+const FSRS_B: f64 = 0.2;
+const FSRS_C: f64 = 0.1;
+const FSRS_W: f64 = 0.3;
+const INITIAL_STABILITY: f64 = 1.0;
+const MIN_STABILITY: f64 = 0.1;
+// A new observation "confirms" the prior one if it's within this much of
+// it; otherwise it "contradicts" and shrinks stability instead.
+const CONFIRM_TOLERANCE: f64 = 0.15;
+// Items whose retrievability has decayed below this are pruned instead of
+// being kept around forever.
+const PRUNE_RETRIEVABILITY: f64 = 0.05;
+
+// R(t) = (1 + t / (9 * S))^-1, the retrievability curve FSRS uses to turn
+// elapsed time + stability into "how well is this still remembered".
+fn retrievability(stability: f64, elapsed_days: f64) -> f64 {
+    (1.0 + elapsed_days.max(0.0) / (9.0 * stability.max(MIN_STABILITY))).recip()
+}
+
+impl MemoryItem {
+    fn elapsed_days(&self, now: chrono::DateTime<chrono::Utc>) -> f64 {
+        (now - self.timestamp).num_seconds() as f64 / 86_400.0
+    }
+
+    fn retrievability_now(&self, now: chrono::DateTime<chrono::Utc>) -> f64 {
+        retrievability(self.stability, self.elapsed_days(now))
+    }
+
+    // Grows stability when `value` confirms this item, shrinks it when it
+    // contradicts, per the FSRS "next stability" update (with `r` the
+    // retrievability this item had right before the new observation
+    // landed).
+    fn observe(&mut self, value: f64, now: chrono::DateTime<chrono::Utc>) -> f64 {
+        let r = self.retrievability_now(now);
+        if (value - self.value).abs() <= CONFIRM_TOLERANCE {
+            self.stability *= 1.0
+                + FSRS_B.exp() * (11.0 - FSRS_DIFFICULTY) * self.stability.powf(-FSRS_C) * ((FSRS_W * (1.0 - r)).exp() - 1.0);
+        } else {
+            self.stability = (self.stability * r).max(MIN_STABILITY);
+        }
+        r
+    }
+}
+
+// Zone feature space the self-organizing map clusters over: behaviorally
+// similar zones land near each other here regardless of name, which is
+// what lets a fresh zone borrow intelligence from its nearest cluster.
+const SOM_FEATURE_COUNT: usize = 4;
+
+fn som_features(zone: &ZoneContext, efficiency: f64) -> [f64; SOM_FEATURE_COUNT] {
+    [zone.activity_level, zone.stress_level, zone.population_density, efficiency / 100.0]
+}
+
+fn feature_distance(a: &[f64; SOM_FEATURE_COUNT], b: &[f64; SOM_FEATURE_COUNT]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt()
+}
+
+// One node's prototype plus the aggregated statistic of every observation
+// it has ever been the best match for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SomNode {
+    prototype: [f64; SOM_FEATURE_COUNT],
+    hits: u64,
+    mean_effectiveness: f64,
+    error_sum: f64,
+    last_used: u64,
+}
+
+impl SomNode {
+    fn new(prototype: [f64; SOM_FEATURE_COUNT], iteration: u64) -> Self {
+        Self { prototype, hits: 0, mean_effectiveness: 0.5, error_sum: 0.0, last_used: iteration }
+    }
+}
+
+// Growing SOM over `SOM_FEATURE_COUNT`-dimensional zone features. Nodes
+// are kept in a `Vec` and treated as a 1D chain (index distance = topo
+// distance) rather than a full grid, since that's the simplest topology
+// that still gives every node well-defined neighbors to nudge and a
+// natural place to insert a split.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfOrganizingMap {
+    nodes: Vec<SomNode>,
+    // The last feature vector observed for each zone, so a lookup that
+    // only has a zone's name (not its live `ZoneContext`) can still find
+    // its best-matching node.
+    zone_features: HashMap<String, [f64; SOM_FEATURE_COUNT]>,
+    iterations: u64,
+}
+
+impl Default for SelfOrganizingMap {
+    fn default() -> Self {
+        Self { nodes: Vec::new(), zone_features: HashMap::new(), iterations: 0 }
+    }
+}
+
+impl SelfOrganizingMap {
+    const INITIAL_LEARNING_RATE: f64 = 0.5;
+    const INITIAL_RADIUS: f64 = 2.0;
+    const DECAY: f64 = 0.01;
+    const MIN_NODES: usize = 2;
+    const MAX_NODES: usize = 24;
+    const MAINTENANCE_INTERVAL: u64 = 25;
+    const SPLIT_ERROR_THRESHOLD: f64 = 0.5;
+    const STALE_WINDOW: u64 = 200;
+
+    fn best_matching_unit(&self, features: &[f64; SOM_FEATURE_COUNT]) -> Option<usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (i, feature_distance(&node.prototype, features)))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i)
+    }
+
+    /// Trains the map on one zone observation: nudges the best-matching
+    /// node and its chain-adjacent neighbors toward `features` with a
+    /// learning rate and neighborhood radius that both decay as
+    /// `iterations` grows, records the observation's effectiveness on the
+    /// best-matching node, then periodically grows/prunes the map.
+    fn observe(&mut self, zone_name: &str, features: [f64; SOM_FEATURE_COUNT], effectiveness: f64) {
+        self.zone_features.insert(zone_name.to_string(), features);
+        self.iterations += 1;
+
+        if self.nodes.is_empty() {
+            self.nodes.push(SomNode::new(features, self.iterations));
+        }
+
+        let bmu = self.best_matching_unit(&features).unwrap_or(0);
+        let learning_rate = Self::INITIAL_LEARNING_RATE / (1.0 + self.iterations as f64 * Self::DECAY);
+        let radius = (Self::INITIAL_RADIUS / (1.0 + self.iterations as f64 * Self::DECAY)).max(f64::EPSILON);
+
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            let topo_distance = (i as f64 - bmu as f64).abs();
+            if topo_distance > radius * 3.0 {
+                continue;
+            }
+            let influence = (-topo_distance.powi(2) / (2.0 * radius.powi(2))).exp();
+            for (w, f) in node.prototype.iter_mut().zip(features) {
+                *w += learning_rate * influence * (f - *w);
+            }
+        }
+
+        let bmu_node = &mut self.nodes[bmu];
+        bmu_node.error_sum += feature_distance(&bmu_node.prototype, &features);
+        bmu_node.hits += 1;
+        bmu_node.last_used = self.iterations;
+        bmu_node.mean_effectiveness += (effectiveness - bmu_node.mean_effectiveness) / bmu_node.hits as f64;
+
+        if self.iterations % Self::MAINTENANCE_INTERVAL == 0 {
+            self.grow();
+            self.prune();
+        }
+    }
+
+    // Splits whichever node has accumulated the highest average
+    // quantization error by inserting a fresh node midway between it and
+    // its farthest chain neighbor, so the map grows resolution where its
+    // current nodes fit the data poorly.
+    fn grow(&mut self) {
+        if self.nodes.len() >= Self::MAX_NODES {
+            return;
+        }
+
+        let worst = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.hits > 0)
+            .map(|(i, n)| (i, n.error_sum / n.hits as f64))
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let Some((index, avg_error)) = worst else { return };
+        if avg_error < Self::SPLIT_ERROR_THRESHOLD {
+            return;
+        }
+
+        let left_distance = if index > 0 { feature_distance(&self.nodes[index].prototype, &self.nodes[index - 1].prototype) } else { -1.0 };
+        let right_distance = if index + 1 < self.nodes.len() { feature_distance(&self.nodes[index].prototype, &self.nodes[index + 1].prototype) } else { -1.0 };
+        let neighbor = if right_distance >= left_distance { index + 1 } else { index - 1 };
+        if neighbor >= self.nodes.len() {
+            return;
+        }
+
+        let mut midpoint = [0.0; SOM_FEATURE_COUNT];
+        for (k, slot) in midpoint.iter_mut().enumerate() {
+            *slot = (self.nodes[index].prototype[k] + self.nodes[neighbor].prototype[k]) / 2.0;
+        }
+
+        let insert_at = index.max(neighbor);
+        self.nodes[index].error_sum = 0.0;
+        self.nodes[index].hits = (self.nodes[index].hits / 2).max(1);
+        self.nodes.insert(insert_at, SomNode::new(midpoint, self.iterations));
+    }
+
+    // Drops nodes nobody has matched in `STALE_WINDOW` iterations, as long
+    // as that leaves at least `MIN_NODES` behind.
+    fn prune(&mut self) {
+        if self.nodes.len() <= Self::MIN_NODES {
+            return;
+        }
+        let iterations = self.iterations;
+        let min_nodes = Self::MIN_NODES;
+        let mut kept = 0;
+        self.nodes.retain(|n| {
+            kept += 1;
+            iterations.saturating_sub(n.last_used) < Self::STALE_WINDOW || kept <= min_nodes
+        });
+    }
+
+    /// The best-matching node's aggregated effectiveness for `zone_name`'s
+    /// last observed feature vector — the fallback `calculate_model_accuracy`
+    /// uses for a zone that hasn't built up enough history of its own yet.
+    fn nearest_cluster_effectiveness(&self, zone_name: &str) -> Option<f64> {
+        let features = self.zone_features.get(zone_name)?;
+        let bmu = self.best_matching_unit(features)?;
+        Some(self.nodes[bmu].mean_effectiveness)
+    }
+
+    /// The best-matching node's prototype for `zone_name` — used by
+    /// `predict_next_state` as a behavioral-cluster prior on activity and
+    /// stress for zones too sparsely observed to forecast from their own
+    /// trend.
+    fn nearest_cluster_features(&self, zone_name: &str) -> Option<[f64; SOM_FEATURE_COUNT]> {
+        let features = self.zone_features.get(zone_name)?;
+        let bmu = self.best_matching_unit(features)?;
+        Some(self.nodes[bmu].prototype)
+    }
+}
+
+pub struct LunaEvolutionEngine {
+    personality: Arc<Mutex<LunaPersonality>>,
+    conversation_history: Arc<Mutex<Vec<Conversation>>>,
+    zone_patterns: Arc<Mutex<HashMap<String, Vec<f64>>>>,
+    biocore_effectiveness: Arc<Mutex<HashMap<String, f64>>>,
+    evolution_metrics: Arc<Mutex<EvolutionMetrics>>,
+    learning_cache: Arc<Mutex<HashMap<String, f64>>>,
+    frequency_optimizer: Arc<Mutex<FrequencyOptimizer>>,
+    evolutionary_tuner: Arc<Mutex<EvolutionaryTuner>>,
+    zone_memory: Arc<Mutex<HashMap<String, Vec<MemoryItem>>>>,
+    self_organizing_map: Arc<Mutex<SelfOrganizingMap>>,
+    state_estimates: Arc<Mutex<StateEstimates>>,
+    detectors: Vec<Box<dyn AnomalyDetector>>,
+    data_dir: PathBuf,
+    alerting: Option<AlertingConfig>,
+    last_alerted: Arc<Mutex<HashMap<String, Instant>>>,
+    effectiveness_model: Arc<Mutex<Option<EffectivenessModel>>>,
+    pattern_templates: Arc<Mutex<HashMap<String, Vec<PatternTemplate>>>>,
+}
+
+/// Where a critical-zone alert gets sent.
+#[derive(Debug, Clone)]
+pub enum AlertTransport {
+    Webhook { endpoint: String },
+}
+
+/// Alerting is disabled unless a `LunaEvolutionEngine` is given one of
+/// these. `min_interval` debounces repeat alerts for the same zone.
+#[derive(Debug, Clone)]
+pub struct AlertingConfig {
+    pub transport: AlertTransport,
+    pub min_interval: Duration,
+}
+
+// Overridable via `ALERT_MIN_INTERVAL_SECS`; alerting itself is only
+// enabled when `ALERT_WEBHOOK_URL` is set.
+const DEFAULT_ALERT_INTERVAL_SECS: u64 = 300;
+
+/// Handle to the background task started by
+/// [`LunaEvolutionEngine::start_runner`].
+pub struct DetectionRunnerHandle {
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl DetectionRunnerHandle {
+    /// Aborts the runner task.
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ZoneAlert {
+    zone_name: String,
+    stress_level: f64,
+    activity_level: f64,
+    recommended_effect: BioCoreEffect,
+    timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+// Everything that needs to survive a restart, checkpointed to a single JSON
+// file under `data_dir`. `personality` and `evolution_metrics` are kept as
+// their own fields (rather than flattened) so they read back as the
+// distinct records they represent, even though they share a file with the
+// rest of the engine's state.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LunaStateSnapshot {
+    personality: Option<LunaPersonality>,
+    evolution_metrics: Option<EvolutionMetrics>,
+    conversation_history: Vec<Conversation>,
+    zone_patterns: HashMap<String, Vec<f64>>,
+    biocore_effectiveness: HashMap<String, f64>,
+    learning_cache: HashMap<String, f64>,
+    #[serde(default)]
+    frequency_optimizer: FrequencyOptimizer,
+    #[serde(default)]
+    evolutionary_tuner: EvolutionaryTuner,
+    #[serde(default)]
+    zone_memory: HashMap<String, Vec<MemoryItem>>,
+    #[serde(default)]
+    self_organizing_map: SelfOrganizingMap,
+    state_estimates: StateEstimates,
+    #[serde(default)]
+    effectiveness_model: Option<EffectivenessModel>,
+    #[serde(default)]
+    pattern_templates: HashMap<String, Vec<PatternTemplate>>,
 }
 
 impl LunaEvolutionEngine {
+    // Discount on the best next-state Q-value in the Bellman update; see
+    // `recommend_biocore`.
+    const Q_GAMMA: f64 = 0.9;
+    // Epsilon-greedy exploration decays from here as `total_interactions`
+    // grows, down to a floor so the policy never stops exploring entirely.
+    const EPSILON_START: f64 = 0.3;
+    const EPSILON_DECAY: f64 = 0.05;
+    const EPSILON_MIN: f64 = 0.02;
+    // Checkpoint to disk at least this often, in addition to every
+    // `check_evolution` milestone transition.
+    const CHECKPOINT_INTERVAL: u64 = 10;
+
     pub fn new() -> Self {
-        let personality = LunaPersonality {
+        let data_dir = std::env::var("LUNA_STATE_DIR").unwrap_or_else(|_| DEFAULT_STATE_DIR.to_string());
+        Self::new_with_data_dir(data_dir)
+    }
+
+    /// Like [`LunaEvolutionEngine::new`], but loads from (and later
+    /// checkpoints to) `data_dir` instead of the `LUNA_STATE_DIR` default.
+    pub fn new_with_data_dir(data_dir: impl Into<PathBuf>) -> Self {
+        let data_dir = data_dir.into();
+        let snapshot = Self::load_snapshot(&data_dir).unwrap_or_default();
+        Self::from_snapshot(snapshot, data_dir)
+    }
+
+    fn load_snapshot(data_dir: &Path) -> Option<LunaStateSnapshot> {
+        let raw = std::fs::read_to_string(data_dir.join(STATE_FILE_NAME)).ok()?;
+        serde_json::from_str(&raw).ok()
+    }
+
+    fn from_snapshot(snapshot: LunaStateSnapshot, data_dir: PathBuf) -> Self {
+        let personality = snapshot.personality.unwrap_or_else(|| LunaPersonality {
             intelligence_level: IntelligenceLevel::Beginner,
             total_interactions: 0,
             learning_rate: 0.1,
@@ -114,9 +1443,9 @@ impl LunaEvolutionEngine {
             memory_retention: 0.7,
             pattern_recognition: 0.3,
             strategic_thinking: 0.2,
-        };
+        });
 
-        let evolution_metrics = EvolutionMetrics {
+        let evolution_metrics = snapshot.evolution_metrics.unwrap_or_else(|| EvolutionMetrics {
             conversations_processed: 0,
             patterns_identified: 0,
             strategies_developed: 0,
@@ -124,15 +1453,40 @@ impl LunaEvolutionEngine {
             success_rate: 0.0,
             evolution_progress: 0.0,
             next_evolution_threshold: 10,
-        };
+        });
+
+        let alerting = std::env::var("ALERT_WEBHOOK_URL").ok().map(|endpoint| {
+            let min_interval_secs = std::env::var("ALERT_MIN_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(DEFAULT_ALERT_INTERVAL_SECS);
+            AlertingConfig {
+                transport: AlertTransport::Webhook { endpoint },
+                min_interval: Duration::from_secs(min_interval_secs),
+            }
+        });
 
         Self {
             personality: Arc::new(Mutex::new(personality)),
-            conversation_history: Arc::new(Mutex::new(Vec::new())),
-            zone_patterns: Arc::new(Mutex::new(HashMap::new())),
-            biocore_effectiveness: Arc::new(Mutex::new(HashMap::new())),
+            conversation_history: Arc::new(Mutex::new(snapshot.conversation_history)),
+            zone_patterns: Arc::new(Mutex::new(snapshot.zone_patterns)),
+            biocore_effectiveness: Arc::new(Mutex::new(snapshot.biocore_effectiveness)),
             evolution_metrics: Arc::new(Mutex::new(evolution_metrics)),
-            learning_cache: Arc::new(Mutex::new(HashMap::new())),
+            learning_cache: Arc::new(Mutex::new(snapshot.learning_cache)),
+            frequency_optimizer: Arc::new(Mutex::new(snapshot.frequency_optimizer)),
+            evolutionary_tuner: Arc::new(Mutex::new(snapshot.evolutionary_tuner)),
+            zone_memory: Arc::new(Mutex::new(snapshot.zone_memory)),
+            self_organizing_map: Arc::new(Mutex::new(snapshot.self_organizing_map)),
+            state_estimates: Arc::new(Mutex::new(snapshot.state_estimates)),
+            detectors: vec![
+                Box::new(ThresholdDetector::default()),
+                Box::new(PatternDetector::default()),
+            ],
+            data_dir,
+            alerting,
+            last_alerted: Arc::new(Mutex::new(HashMap::new())),
+            effectiveness_model: Arc::new(Mutex::new(snapshot.effectiveness_model)),
+            pattern_templates: Arc::new(Mutex::new(snapshot.pattern_templates)),
         }
     }
 
@@ -152,12 +1506,65 @@ impl LunaEvolutionEngine {
             &interaction_type,
         ).await?;
 
-        // Calculate effectiveness
-        let effectiveness_score = self.calculate_response_effectiveness(&luna_response);
-
         // Apply learning and evolution
         self.apply_learning(&user_message, &luna_response, &zone_context).await?;
 
+        // Escalate to an emergency response if the zone's updated activity
+        // series now matches a known anomaly, rather than only ever
+        // comparing stress_level > 0.7 inline.
+        let zone_anomaly = zone_context.as_ref().and_then(|zone| {
+            self.detect_anomalies()
+                .into_iter()
+                .filter(|a| a.zone_name == zone.zone_name)
+                .max_by(|a, b| a.severity.partial_cmp(&b.severity).unwrap_or(std::cmp::Ordering::Equal))
+        });
+        let (interaction_type, luna_response) = match zone_anomaly {
+            Some(anomaly) => (
+                InteractionType::EmergencyResponse,
+                format!(
+                    "🚨 CRITICAL ({} detector, severity {:.2}): apply emergency BioCore protocol to {} zone immediately. {}",
+                    anomaly.detector, anomaly.severity, anomaly.zone_name, luna_response
+                ),
+            ),
+            None => (interaction_type, luna_response),
+        };
+
+        // Pick a BioCore effect for the zone, score how effective applying
+        // it is predicted to be, and feed that back so the policy and the
+        // effectiveness model both improve over time.
+        let biocore_applied = zone_context.as_ref().map(|zone| self.recommend_biocore(zone));
+        let effectiveness_score =
+            self.estimate_effectiveness(&zone_context, &biocore_applied, &luna_response, timestamp);
+        if let (Some(zone), Some(effect)) = (&zone_context, &biocore_applied) {
+            let state = ZoneState::from_context(zone);
+            let action = BioCoreAction::from_effect(effect);
+            let alpha = self.personality.lock().unwrap().learning_rate;
+            self.update_q_value(&state, &action, effectiveness_score, alpha);
+            self.maybe_alert_critical_zone(zone, effect, &interaction_type);
+
+            let key = format!("{}_{}", effect.plant_name, effect.drug_name);
+            self.biocore_effectiveness.lock().unwrap().insert(key, effectiveness_score);
+        }
+
+        // Score this zone's optimization frequency with a reward made of
+        // how effective the response just generated for it was, plus a
+        // bonus for whatever efficiency gain happened since its last
+        // pending step, then TD-update the policy and pick the next
+        // frequency to expose through `calculate_optimization_frequency`.
+        if let Some(zone) = &zone_context {
+            let efficiency = self.calculate_zone_efficiency(zone);
+            let features = frequency_state_features(zone, efficiency);
+            let alpha = self.personality.lock().unwrap().learning_rate;
+            let mut optimizer = self.frequency_optimizer.lock().unwrap();
+            let efficiency_bonus = optimizer
+                .pending
+                .get(&zone.zone_name)
+                .map(|prev| (efficiency - prev.efficiency) / 100.0)
+                .unwrap_or(0.0);
+            let reward = self.calculate_response_effectiveness(&luna_response) + efficiency_bonus;
+            optimizer.step(&zone.zone_name, features, reward, alpha);
+        }
+
         // Create conversation record
         let conversation = Conversation {
             id: conversation_id,
@@ -166,7 +1573,7 @@ impl LunaEvolutionEngine {
             luna_response: luna_response.clone(),
             interaction_type,
             zone_context,
-            biocore_applied: None,
+            biocore_applied,
             effectiveness_score,
             learning_weight: self.calculate_learning_weight(&interaction_type),
         };
@@ -181,11 +1588,253 @@ impl LunaEvolutionEngine {
         let personality = self.update_personality().await?;
 
         // Check for evolution
-        self.check_evolution().await?;
+        let evolved = self.check_evolution().await?;
+
+        // Checkpoint to disk on every evolution milestone, and otherwise at
+        // a fixed interval, so the accumulated learning survives a restart.
+        if evolved || personality.total_interactions % Self::CHECKPOINT_INTERVAL == 0 {
+            self.persist().await?;
+        }
 
         Ok((luna_response, personality))
     }
 
+    /// Returns a clone of the most recently recorded conversation, if any.
+    pub fn latest_conversation(&self) -> Option<Conversation> {
+        self.conversation_history.lock().unwrap().last().cloned()
+    }
+
+    /// Returns a snapshot of the current evolution metrics.
+    pub fn evolution_metrics_snapshot(&self) -> EvolutionMetrics {
+        self.evolution_metrics.lock().unwrap().clone()
+    }
+
+    /// Returns a snapshot of the current personality, without processing a
+    /// conversation.
+    pub fn personality_snapshot(&self) -> LunaPersonality {
+        self.personality.lock().unwrap().clone()
+    }
+
+    /// The memory half-life (in days) LUNA currently maintains for
+    /// `zone_name`'s recorded effectiveness, i.e. how long it takes the
+    /// most recent observation's retrievability to decay to 0.5, per the
+    /// FSRS-style model in [`MemoryItem`]. `None` until the zone has at
+    /// least one recorded observation.
+    pub fn optimal_retention(&self, zone_name: &str) -> Option<f64> {
+        let memory = self.zone_memory.lock().unwrap();
+        let stability = memory.get(zone_name)?.last()?.stability;
+        Some(9.0 * stability)
+    }
+
+    /// Picks the BioCore effect to apply to `zone`, via an epsilon-greedy
+    /// policy over a tabular Q-table keyed on the discretized zone state.
+    /// Epsilon decays as `total_interactions` grows, so Luna explores less
+    /// once it has seen more conversations.
+    pub fn recommend_biocore(&self, zone: &ZoneContext) -> BioCoreEffect {
+        let state = ZoneState::from_context(zone);
+        let catalog = biocore_catalog();
+        let total_interactions = self.personality.lock().unwrap().total_interactions;
+        let epsilon = (Self::EPSILON_START / (1.0 + total_interactions as f64 * Self::EPSILON_DECAY))
+            .max(Self::EPSILON_MIN);
+
+        if rand::random::<f64>() < epsilon {
+            let index = (rand::random::<f64>() * catalog.len() as f64) as usize % catalog.len();
+            return catalog[index].clone();
+        }
+
+        let table = self.state_estimates.lock().unwrap();
+        let q_for_state = table.get(&state);
+        let model = self.effectiveness_model.lock().unwrap();
+        let timestamp = chrono::Utc::now();
+        let samples = self.zone_patterns.lock().unwrap().get(&zone.zone_name).cloned().unwrap_or_default();
+        let trend = linear_trend(&samples);
+        let variance = std_dev(&samples);
+
+        catalog
+            .iter()
+            .max_by(|a, b| {
+                let q_a = q_for_state.and_then(|q| q.get(&BioCoreAction::from_effect(a))).copied().unwrap_or(0.0);
+                let q_b = q_for_state.and_then(|q| q.get(&BioCoreAction::from_effect(b))).copied().unwrap_or(0.0);
+                // Once `EffectivenessModel` has enough labeled data, layer its
+                // predicted effectiveness on top of the learned Q-value so
+                // ranking improves with data instead of only ever reflecting
+                // past reward; with no model yet, this is a no-op.
+                let predicted_a = model
+                    .as_ref()
+                    .map(|m| m.predict(&effectiveness_features(zone, a, timestamp, trend, variance)))
+                    .unwrap_or(0.0);
+                let predicted_b = model
+                    .as_ref()
+                    .map(|m| m.predict(&effectiveness_features(zone, b, timestamp, trend, variance)))
+                    .unwrap_or(0.0);
+                (q_a + predicted_a).partial_cmp(&(q_b + predicted_b)).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .cloned()
+            .unwrap_or_else(|| catalog[0].clone())
+    }
+
+    // Applies the Q-learning update `Q(s,a) += alpha * (r + gamma *
+    // max_a' Q(s',a') - Q(s,a))`. Since the next zone reading isn't
+    // available synchronously when a conversation completes, `next_state`
+    // is the same discretized state the action was chosen from.
+    fn update_q_value(&self, state: &ZoneState, action: &BioCoreAction, reward: f64, alpha: f64) {
+        let mut table = self.state_estimates.lock().unwrap();
+        let max_next_q = table
+            .get(state)
+            .map(|actions| actions.values().cloned().fold(f64::MIN, f64::max))
+            .filter(|q| q.is_finite())
+            .unwrap_or(0.0);
+
+        let actions = table.entry(state.clone()).or_insert_with(HashMap::new);
+        let current_q = *actions.get(action).unwrap_or(&0.0);
+        let updated_q = current_q + alpha * (reward + Self::Q_GAMMA * max_next_q - current_q);
+        actions.insert(action.clone(), updated_q);
+    }
+
+    /// Runs every registered [`AnomalyDetector`] over every zone's recorded
+    /// activity series and returns whatever they flag.
+    pub fn detect_anomalies(&self) -> Vec<ZoneAnomaly> {
+        let patterns = self.zone_patterns.lock().unwrap();
+        patterns
+            .iter()
+            .flat_map(|(zone_name, samples)| {
+                self.detectors.iter().filter_map(move |detector| detector.detect(zone_name, samples))
+            })
+            .collect()
+    }
+
+    /// Spawns a background task that wakes every `interval` and scans
+    /// every zone in `zone_patterns` for anomalies and forecast drift,
+    /// acting on them the same way an interactive conversation would —
+    /// without waiting for a user to ask. This is what makes the
+    /// `Autonomous`/`Master` levels' "real-time monitoring, self-healing
+    /// protocols" text an actual running loop instead of flavor text.
+    /// Dropping the returned [`DetectionRunnerHandle`] does not stop the
+    /// task; call [`DetectionRunnerHandle::stop`] for that.
+    pub fn start_runner(self: Arc<Self>, interval: Duration) -> DetectionRunnerHandle {
+        let task = tokio::spawn(async move {
+            loop {
+                sleep(interval).await;
+                self.run_detection_cycle().await;
+            }
+        });
+        DetectionRunnerHandle { task }
+    }
+
+    // One sweep of the detection runner: for every zone with enough
+    // history, runs the anomaly detectors and the forecaster, and - if
+    // either one flags something worth acting on - picks a BioCore
+    // effect, feeds it through the same Q-update and alerting path as
+    // `process_conversation`, and records a synthetic `Conversation` with
+    // no `user_message` so the runner's activity still shows up in
+    // history and metrics.
+    async fn run_detection_cycle(&self) {
+        let zone_names: Vec<String> = self.zone_patterns.lock().unwrap().keys().cloned().collect();
+
+        for zone_name in zone_names {
+            let patterns = match self.zone_patterns.lock().unwrap().get(&zone_name) {
+                Some(samples) if samples.len() >= 3 => samples.clone(),
+                _ => continue,
+            };
+
+            let anomaly = self.detectors.iter().find_map(|d| d.detect(&zone_name, &patterns));
+            let (forecast_activity, forecast_stress) = self.predict_next_state(&zone_name, &patterns);
+            if anomaly.is_none() && forecast_stress <= 0.6 {
+                continue;
+            }
+
+            let zone = ZoneContext {
+                zone_name: zone_name.clone(),
+                activity_level: forecast_activity,
+                stress_level: forecast_stress,
+                population_density: 0.5,
+                primary_function: "autonomous_monitoring".to_string(),
+            };
+            let effect = self.recommend_biocore(&zone);
+            let interaction_type = if anomaly.is_some() {
+                InteractionType::EmergencyResponse
+            } else {
+                InteractionType::SystemOptimization
+            };
+
+            let luna_response = match &anomaly {
+                Some(a) => format!(
+                    "🌙 Autonomous monitoring: {} detector flagged {} zone (severity {:.2}); recommending {} + {}.",
+                    a.detector, zone_name, a.severity, effect.plant_name, effect.drug_name
+                ),
+                None => format!(
+                    "🌙 Autonomous monitoring: {} zone forecast trending toward stress {:.2}; recommending {} + {}.",
+                    zone_name, forecast_stress, effect.plant_name, effect.drug_name
+                ),
+            };
+
+            let effectiveness_score = self.calculate_predictive_accuracy(&zone, &patterns);
+            let state = ZoneState::from_context(&zone);
+            let action = BioCoreAction::from_effect(&effect);
+            let alpha = self.personality.lock().unwrap().learning_rate;
+            self.update_q_value(&state, &action, effectiveness_score, alpha);
+            self.maybe_alert_critical_zone(&zone, &effect, &interaction_type);
+
+            let conversation = Conversation {
+                id: Uuid::new_v4().to_string(),
+                timestamp: chrono::Utc::now(),
+                user_message: String::new(),
+                luna_response,
+                interaction_type: interaction_type.clone(),
+                zone_context: Some(zone),
+                biocore_applied: Some(effect),
+                effectiveness_score,
+                learning_weight: self.calculate_learning_weight(&interaction_type),
+            };
+            self.conversation_history.lock().unwrap().push(conversation);
+            self.evolution_metrics.lock().unwrap().optimizations_applied += 1;
+        }
+    }
+
+    // Fires a webhook alert for `zone` when alerting is configured and the
+    // zone is critical (high stress, or the conversation already escalated
+    // to an `EmergencyResponse`), debounced per zone by `min_interval` so a
+    // sustained incident doesn't page the endpoint on every conversation.
+    // Dispatched on the shared tokio runtime so the POST never blocks
+    // `process_conversation`.
+    fn maybe_alert_critical_zone(
+        &self,
+        zone: &ZoneContext,
+        effect: &BioCoreEffect,
+        interaction_type: &InteractionType,
+    ) {
+        let Some(alerting) = &self.alerting else { return };
+        let is_critical =
+            zone.stress_level > 0.7 || matches!(interaction_type, InteractionType::EmergencyResponse);
+        if !is_critical {
+            return;
+        }
+
+        {
+            let mut last_alerted = self.last_alerted.lock().unwrap();
+            if let Some(last) = last_alerted.get(&zone.zone_name) {
+                if last.elapsed() < alerting.min_interval {
+                    return;
+                }
+            }
+            last_alerted.insert(zone.zone_name.clone(), Instant::now());
+        }
+
+        let alert = ZoneAlert {
+            zone_name: zone.zone_name.clone(),
+            stress_level: zone.stress_level,
+            activity_level: zone.activity_level,
+            recommended_effect: effect.clone(),
+            timestamp: chrono::Utc::now(),
+        };
+        let AlertTransport::Webhook { endpoint } = alerting.transport.clone();
+        tokio::spawn(async move {
+            if let Err(err) = reqwest::Client::new().post(&endpoint).json(&alert).send().await {
+                tracing::warn!(zone = %alert.zone_name, error = %err, "failed to dispatch zone alert");
+            }
+        });
+    }
+
     async fn generate_contextual_response(
         &self,
         user_message: &str,
@@ -358,22 +2007,23 @@ impl LunaEvolutionEngine {
         match zone_context {
             Some(zone) => {
                 let effectiveness = cache.get(&format!("{}_effectiveness", zone.zone_name));
+                let effect = self.recommend_biocore(zone);
                 match effectiveness {
                     Some(eff) => {
                         if zone.stress_level > 0.7 {
                             format!(
-                                "CRITICAL: Apply emergency BioCore protocol to {} zone. Use Turmeric+DrugB (synergy: 0.90) for immediate stress reduction",
-                                zone.zone_name
+                                "CRITICAL: Apply emergency BioCore protocol to {} zone. Use {}+{} (synergy: {:.2}) for immediate stress reduction",
+                                zone.zone_name, effect.plant_name, effect.drug_name, effect.synergy_score
                             )
                         } else if *eff > 0.8 {
                             format!(
-                                "Optimize {} zone with Ginseng+DrugC (activating synergy: 0.75) for enhanced performance",
-                                zone.zone_name
+                                "Optimize {} zone with {}+{} (synergy: {:.2}) for enhanced performance",
+                                zone.zone_name, effect.plant_name, effect.drug_name, effect.synergy_score
                             )
                         } else {
                             format!(
-                                "Implement predictive BioCore management for {} zone with Basil+DrugD (balancing synergy: 0.65)",
-                                zone.zone_name
+                                "Implement predictive BioCore management for {} zone with {}+{} (synergy: {:.2})",
+                                zone.zone_name, effect.plant_name, effect.drug_name, effect.synergy_score
                             )
                         }
                     }
@@ -428,11 +2078,19 @@ impl LunaEvolutionEngine {
                 let zone_patterns = patterns.get(&zone.zone_name);
                 match zone_patterns {
                     Some(patterns) => {
-                        let prediction = self.predict_next_state(patterns);
+                        let prediction = self.predict_next_state(&zone.zone_name, patterns);
+                        let cycle = dominant_frequency(patterns)
+                            .filter(|peak| peak.relative_power > 0.3)
+                            .map(|peak| format!(
+                                ", detected ~{:.1}-sample cycle ({:.0}% spectral power)",
+                                peak.period, peak.relative_power * 100.0
+                            ))
+                            .unwrap_or_default();
                         format!(
-                            "Predicted {} zone state in 1 hour: activity {:.2}, stress {:.2}, confidence {:.1}%",
+                            "Predicted {} zone state in 1 hour: activity {:.2}, stress {:.2}, confidence {:.1}%{}",
                             zone.zone_name, prediction.0, prediction.1,
-                            self.calculate_prediction_confidence(patterns) * 100.0
+                            self.calculate_prediction_confidence(&zone.zone_name, patterns) * 100.0,
+                            cycle
                         )
                     }
                     None => format!(
@@ -573,28 +2231,11 @@ impl LunaEvolutionEngine {
 
     // Helper methods for calculations
     fn calculate_variance(&self, values: &[f64]) -> f64 {
-        let mean = values.iter().sum::<f64>() / values.len() as f64;
-        let variance = values.iter()
-            .map(|x| (x - mean).powi(2))
-            .sum::<f64>() / values.len() as f64;
-        variance.sqrt()
+        std_dev(values)
     }
 
     fn calculate_trend(&self, values: &[f64]) -> f64 {
-        if values.len() < 2 {
-            return 0.0;
-        }
-        
-        let n = values.len() as f64;
-        let sum_x: f64 = (0..values.len()).map(|i| i as f64).sum();
-        let sum_y: f64 = values.iter().sum();
-        let sum_xy: f64 = values.iter().enumerate()
-            .map(|(i, y)| i as f64 * y)
-            .sum();
-        let sum_x2: f64 = (0..values.len()).map(|i| (i as f64).powi(2)).sum();
-        
-        let slope = (n * sum_xy - sum_x * sum_y) / (n * sum_x2 - sum_x.powi(2));
-        slope
+        linear_trend(values)
     }
 
     fn calculate_cross_zone_impact(&self, zone: &ZoneContext) -> f64 {
@@ -608,30 +2249,107 @@ impl LunaEvolutionEngine {
         let variance = self.calculate_variance(patterns);
         let base_accuracy = 0.8;
         let variance_penalty = (variance / 10.0).min(0.3);
-        base_accuracy - variance_penalty
+        // A strong fit against a stored template is direct evidence the
+        // zone is on its learned normal, on top of the variance penalty
+        // alone (which only measures noisiness, not whether it matches
+        // anything LUNA recognizes).
+        let correlation_bonus = self.correlation_score(&zone.zone_name, patterns).unwrap_or(0.0) * 0.15;
+        (base_accuracy - variance_penalty + correlation_bonus).clamp(0.0, 0.99)
+    }
+
+    // Best Pearson correlation between `patterns` and any of `zone_name`'s
+    // stored template windows, in the time domain (unlike `nearest_template`,
+    // which compares spectral fingerprints). `None` when the zone has no
+    // templates yet, so callers can tell "no signal" apart from "matches
+    // nothing", which a numeric 0.0 can't.
+    fn correlation_score(&self, zone_name: &str, patterns: &[f64]) -> Option<f64> {
+        let templates = self.pattern_templates.lock().unwrap();
+        let candidates = templates.get(zone_name)?;
+        candidates
+            .iter()
+            .filter(|t| !t.raw_window.is_empty())
+            .map(|t| pearson_correlation(patterns, &t.raw_window))
+            .fold(None, |best: Option<f64>, score| Some(best.map_or(score, |b: f64| b.max(score))))
     }
 
-    fn predict_next_state(&self, patterns: &[f64]) -> (f64, f64) {
+    // Predicts the next activity/stress sample by matching `patterns`'
+    // spectral fingerprint against `zone_name`'s labelled `PatternTemplate`s
+    // and returning the nearest one's recorded continuation. Falls back to
+    // trend-only extrapolation (the previous approach) until the zone has
+    // accumulated enough history to have any templates at all.
+    fn predict_next_state(&self, zone_name: &str, patterns: &[f64]) -> (f64, f64) {
         if patterns.len() < 3 {
-            return (0.5, 0.5);
+            // Too little history of its own: borrow the behavioral prior
+            // of whichever SOM node this zone's last reading matched best,
+            // rather than always guessing the same (0.5, 0.5).
+            return self
+                .self_organizing_map
+                .lock()
+                .unwrap()
+                .nearest_cluster_features(zone_name)
+                .map(|f| (f[0].clamp(0.0, 1.0), f[1].clamp(0.0, 1.0)))
+                .unwrap_or((0.5, 0.5));
         }
-        
-        let recent = &patterns[patterns.len()-3..];
-        let avg_activity = recent.iter().sum::<f64>() / 3.0;
-        let avg_stress = 0.4; // Simplified stress prediction
-        
-        (avg_activity, avg_stress)
+
+        match self.nearest_template(zone_name, patterns) {
+            Some((template, _distance)) => {
+                (template.next_activity.clamp(0.0, 1.0), template.next_stress.clamp(0.0, 1.0))
+            }
+            None => {
+                let trend = self.calculate_trend(patterns);
+                let forecast = patterns.last().copied().unwrap_or(0.5) + trend;
+                (forecast.clamp(0.0, 1.0), 0.4)
+            }
+        }
+    }
+
+    // Labels `window`'s current spectral fingerprint with what the zone's
+    // activity/stress actually did next, bounding the replay buffer at
+    // `MAX_TEMPLATES_PER_ZONE` the same way `zone_patterns` bounds its own
+    // history.
+    fn record_pattern_template(&self, zone_name: &str, window: &[f64], next_activity: f64, next_stress: f64) {
+        let features = normalize_features(&spectral_features(window));
+        let raw_window = fixed_window(window, TEMPLATE_WINDOW_SIZE);
+        let mut templates = self.pattern_templates.lock().unwrap();
+        let entry = templates.entry(zone_name.to_string()).or_insert_with(Vec::new);
+        entry.push(PatternTemplate { features, next_activity, next_stress, raw_window });
+        if entry.len() > MAX_TEMPLATES_PER_ZONE {
+            entry.remove(0);
+        }
+    }
+
+    // Finds `zone_name`'s labelled template whose spectral fingerprint is
+    // closest (Euclidean, on L2-normalized features) to `patterns`'
+    // current window, along with that distance.
+    fn nearest_template(&self, zone_name: &str, patterns: &[f64]) -> Option<(PatternTemplate, f64)> {
+        let templates = self.pattern_templates.lock().unwrap();
+        let candidates = templates.get(zone_name)?;
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let query = normalize_features(&spectral_features(patterns));
+        candidates
+            .iter()
+            .map(|template| (template.clone(), euclidean_distance(&query, &template.features)))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
     }
 
-    fn calculate_prediction_confidence(&self, patterns: &[f64]) -> f64 {
+    // confidence = 1 - (distance to nearest template / 2), since Euclidean
+    // distance between two unit vectors is bounded by 2. Falls back to the
+    // old variance-penalty guess when the zone has no templates yet.
+    fn calculate_prediction_confidence(&self, zone_name: &str, patterns: &[f64]) -> f64 {
         if patterns.len() < 5 {
             return 0.3;
         }
-        
-        let variance = self.calculate_variance(patterns);
-        let base_confidence = 0.9;
-        let variance_penalty = (variance / 5.0).min(0.4);
-        base_confidence - variance_penalty
+
+        match self.nearest_template(zone_name, patterns) {
+            Some((_, distance)) => (1.0 - distance / 2.0).clamp(0.0, 0.99),
+            None => {
+                let variance = self.calculate_variance(patterns);
+                (0.9 - (variance / 5.0).min(0.4)).min(0.99)
+            }
+        }
     }
 
     fn calculate_zone_efficiency(&self, zone: &ZoneContext) -> f64 {
@@ -642,20 +2360,17 @@ impl LunaEvolutionEngine {
         (activity_efficiency - stress_penalty + density_bonus).max(0.0).min(100.0)
     }
 
+    // Both used to be their own fixed-multiplier formulas; now they read
+    // off the same learned optimization frequency from `FrequencyOptimizer`
+    // (see its `step`), scaling the old `base_rate` by how far the policy
+    // has moved the frequency from its `DEFAULT_FREQUENCY` starting point.
     fn calculate_adaptation_rate(&self, zone: &ZoneContext) -> f64 {
         let base_rate = 0.1;
-        let stress_factor = if zone.stress_level > 0.6 { 1.5 } else { 1.0 };
-        let activity_factor = zone.activity_level;
-        
-        base_rate * stress_factor * activity_factor
+        base_rate * (self.calculate_optimization_frequency(zone) / FrequencyOptimizer::DEFAULT_FREQUENCY)
     }
 
     fn calculate_optimization_frequency(&self, zone: &ZoneContext) -> f64 {
-        let base_frequency = 2.0; // per hour
-        let stress_multiplier = if zone.stress_level > 0.5 { 2.0 } else { 1.0 };
-        let activity_multiplier = zone.activity_level;
-        
-        base_frequency * stress_multiplier * activity_multiplier
+        self.frequency_optimizer.lock().unwrap().frequency_for(&zone.zone_name)
     }
 
     fn get_data_point_count(&self, zone: &ZoneContext) -> u64 {
@@ -671,8 +2386,20 @@ impl LunaEvolutionEngine {
         let base_accuracy = 0.85;
         let stress_penalty = zone.stress_level * 0.1;
         let activity_bonus = zone.activity_level * 0.05;
-        
-        (base_accuracy - stress_penalty + activity_bonus).max(0.5).min(0.99)
+        let heuristic = (base_accuracy - stress_penalty + activity_bonus).max(0.5).min(0.99);
+
+        let history_len = self.zone_patterns.lock().unwrap().get(&zone.zone_name).map(Vec::len).unwrap_or(0);
+        if history_len >= MIN_TEMPLATE_HISTORY {
+            return heuristic;
+        }
+
+        // Sparsely-seen zone: blend in its SOM best-matching node's
+        // effectiveness, since a behaviorally similar zone elsewhere
+        // already tells us more about this one than its own thin history.
+        match self.self_organizing_map.lock().unwrap().nearest_cluster_effectiveness(&zone.zone_name) {
+            Some(cluster_effectiveness) => (heuristic * 0.4 + cluster_effectiveness * 0.6).max(0.5).min(0.99),
+            None => heuristic,
+        }
     }
 
     fn calculate_improvement_rate(&self, zone: &ZoneContext) -> f64 {
@@ -688,19 +2415,37 @@ impl LunaEvolutionEngine {
         let length_score = if response.len() > 100 { 0.8 } else { 0.6 };
         let keyword_score = if response.contains("BioCore") { 0.9 } else { 0.7 };
         let context_score = if response.contains("zone") { 0.8 } else { 0.6 };
-        
+
         (length_score + keyword_score + context_score) / 3.0
     }
 
-    fn calculate_learning_weight(&self, interaction_type: &InteractionType) -> f64 {
-        match interaction_type {
-            InteractionType::ZoneAnalysis => 0.8,
-            InteractionType::BioCoreRecommendation => 0.9,
-            InteractionType::SystemOptimization => 1.0,
-            InteractionType::StrategicPlanning => 0.95,
-            InteractionType::GeneralInquiry => 0.5,
-            InteractionType::EmergencyResponse => 1.0,
+    // Predicts how effective `biocore_applied` will be for `zone_context`
+    // using `EffectivenessModel`, falling back to the text-keyword
+    // heuristic above when there's no zone/effect to build features from, or
+    // the model hasn't seen `MIN_LABELED_CONVERSATIONS` yet.
+    fn estimate_effectiveness(
+        &self,
+        zone_context: &Option<ZoneContext>,
+        biocore_applied: &Option<BioCoreEffect>,
+        luna_response: &str,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> f64 {
+        if let (Some(zone), Some(effect)) = (zone_context, biocore_applied) {
+            if let Some(model) = self.effectiveness_model.lock().unwrap().as_ref() {
+                let samples = self.zone_patterns.lock().unwrap().get(&zone.zone_name).cloned().unwrap_or_default();
+                let trend = linear_trend(&samples);
+                let variance = std_dev(&samples);
+                let features = effectiveness_features(zone, effect, timestamp, trend, variance);
+                return model.predict(&features);
+            }
         }
+        self.calculate_response_effectiveness(luna_response)
+    }
+
+    // Used to be fixed per-InteractionType constants; now reads the live
+    // genome `EvolutionaryTuner` has promoted, per its `record` above.
+    fn calculate_learning_weight(&self, interaction_type: &InteractionType) -> f64 {
+        self.evolutionary_tuner.lock().unwrap().live_genome().interaction_weight(interaction_type)
     }
 
     async fn apply_learning(
@@ -713,9 +2458,25 @@ impl LunaEvolutionEngine {
         if let Some(zone) = zone_context {
             let mut patterns = self.zone_patterns.lock().unwrap();
             let zone_patterns = patterns.entry(zone.zone_name.clone()).or_insert_with(Vec::new);
+
+            // Label the window as it stood *before* this sample lands with
+            // what actually happened next, so `predict_next_state` has
+            // something to match future windows against.
+            if zone_patterns.len() >= MIN_TEMPLATE_HISTORY {
+                self.record_pattern_template(
+                    &zone.zone_name,
+                    zone_patterns.as_slice(),
+                    zone.activity_level,
+                    zone.stress_level,
+                );
+            }
+
             zone_patterns.push(zone.activity_level);
-            
-            // Keep only last 50 data points
+
+            // This is the raw activity series `dominant_frequency`/
+            // `spectral_features` need evenly sampled, so it stays a fixed
+            // window rather than switching to retrievability-based decay
+            // like the effectiveness memory below.
             if zone_patterns.len() > 50 {
                 zone_patterns.remove(0);
             }
@@ -724,12 +2485,38 @@ impl LunaEvolutionEngine {
         // Update learning cache
         let mut cache = self.learning_cache.lock().unwrap();
         let effectiveness = self.calculate_response_effectiveness(luna_response);
-        
+
+        // Feed this sample into whichever genome the tuner currently has
+        // under evaluation; see `EvolutionaryTuner::record`.
+        self.evolutionary_tuner.lock().unwrap().record(effectiveness);
+
         if let Some(zone) = zone_context {
+            let timestamp = chrono::Utc::now();
             let cache_key = format!("{}_effectiveness", zone.zone_name);
-            let current_effectiveness = cache.get(&cache_key).unwrap_or(&0.5);
-            let new_effectiveness = (current_effectiveness * 0.8 + effectiveness * 0.2);
+            let current_effectiveness = *cache.get(&cache_key).unwrap_or(&0.5);
+
+            // Blend weight used to be a flat 0.2; now it's how retrievable
+            // the zone's last recorded effectiveness still is, so a zone
+            // observed again soon after its last reading takes on most of
+            // the new observation, while one that's been quiet for a while
+            // moves its cached average only slightly.
+            let mut memory = self.zone_memory.lock().unwrap();
+            let items = memory.entry(zone.zone_name.clone()).or_insert_with(Vec::new);
+            let blend_weight = match items.last_mut() {
+                Some(last) => last.observe(effectiveness, timestamp),
+                None => 1.0,
+            };
+            items.push(MemoryItem { value: effectiveness, timestamp, stability: INITIAL_STABILITY });
+            items.retain(|item| item.retrievability_now(timestamp) >= PRUNE_RETRIEVABILITY);
+
+            let new_effectiveness = current_effectiveness * (1.0 - blend_weight) + effectiveness * blend_weight;
             cache.insert(cache_key, new_effectiveness);
+
+            // Train the cross-zone behavioral cluster map on this
+            // observation, so `predict_next_state`/`calculate_model_accuracy`
+            // have a SOM node to fall back to for zones thinner on history.
+            let efficiency = self.calculate_zone_efficiency(zone);
+            self.self_organizing_map.lock().unwrap().observe(&zone.zone_name, som_features(zone, efficiency), effectiveness);
         }
 
         // Simulate learning delay
@@ -757,25 +2544,30 @@ impl LunaEvolutionEngine {
             _ => IntelligenceLevel::Autonomous,
         };
         
-        // Update other personality traits
-        personality.learning_rate = (0.1 + learning_progress * 0.01).min(0.5);
-        personality.adaptation_speed = (0.05 + learning_progress * 0.005).min(0.3);
-        personality.confidence_score = (0.5 + learning_progress * 0.02).min(0.95);
-        personality.memory_retention = (0.7 + learning_progress * 0.01).min(0.95);
-        personality.pattern_recognition = (0.3 + learning_progress * 0.02).min(0.9);
-        personality.strategic_thinking = (0.2 + learning_progress * 0.015).min(0.85);
+        // Update other personality traits, growing each at the rate the
+        // live genome has evolved for it rather than a fixed constant.
+        let growth = self.evolutionary_tuner.lock().unwrap().live_genome().growth_rates();
+        personality.learning_rate = (0.1 + learning_progress * growth[0]).min(0.5);
+        personality.adaptation_speed = (0.05 + learning_progress * growth[1]).min(0.3);
+        personality.confidence_score = (0.5 + learning_progress * growth[2]).min(0.95);
+        personality.memory_retention = (0.7 + learning_progress * growth[3]).min(0.95);
+        personality.pattern_recognition = (0.3 + learning_progress * growth[4]).min(0.9);
+        personality.strategic_thinking = (0.2 + learning_progress * growth[5]).min(0.85);
         
         Ok(personality.clone())
     }
 
-    async fn check_evolution(&self) -> Result<(), Box<dyn std::error::Error>> {
+    // Returns whether a milestone was reached, so callers can checkpoint on
+    // transitions instead of only on a fixed interval.
+    async fn check_evolution(&self) -> Result<bool, Box<dyn std::error::Error>> {
         let mut metrics = self.evolution_metrics.lock().unwrap();
         let personality = self.personality.lock().unwrap();
-        
+
         metrics.conversations_processed = personality.total_interactions;
-        
+
         // Check for evolution milestones
         let evolution_thresholds = vec![5, 10, 20, 50, 100];
+        let mut evolved = false;
         for threshold in evolution_thresholds {
             if personality.total_interactions == threshold {
                 metrics.patterns_identified += threshold / 2;
@@ -783,13 +2575,95 @@ impl LunaEvolutionEngine {
                 metrics.optimizations_applied += threshold / 4;
                 metrics.success_rate = (metrics.optimizations_applied as f64 / metrics.conversations_processed as f64) * 100.0;
                 metrics.evolution_progress = (personality.total_interactions as f64 / 100.0) * 100.0;
-                
+                evolved = true;
+
                 // Trigger evolution event
-                println!("🌙 LUNA EVOLUTION: Reached {} interactions - Intelligence Level: {:?}", 
+                println!("🌙 LUNA EVOLUTION: Reached {} interactions - Intelligence Level: {:?}",
                     personality.total_interactions, personality.intelligence_level);
             }
         }
-        
+
+        drop(metrics);
+        drop(personality);
+
+        if evolved {
+            self.retrain_effectiveness_model();
+        }
+
+        // A zone whose activity no longer correlates with anything it's
+        // been seen doing before is itself an anomaly signal, on top of
+        // the threshold/pattern detectors `process_conversation` already
+        // checks inline - escalate the conversation just recorded the same
+        // way those do.
+        self.escalate_on_correlation_anomaly();
+
+        Ok(evolved)
+    }
+
+    // Near-zero or negative across every stored template for its own zone
+    // means activity just recorded doesn't resemble anything LUNA has
+    // learned to recognize there, i.e. the zone is off its learned normal.
+    const CORRELATION_ANOMALY_THRESHOLD: f64 = 0.1;
+
+    fn escalate_on_correlation_anomaly(&self) {
+        let mut history = self.conversation_history.lock().unwrap();
+        let Some(conversation) = history.last_mut() else { return };
+        if matches!(conversation.interaction_type, InteractionType::EmergencyResponse) {
+            return;
+        }
+        let Some(zone) = &conversation.zone_context else { return };
+
+        let patterns = match self.zone_patterns.lock().unwrap().get(&zone.zone_name) {
+            Some(p) if p.len() >= 3 => p.clone(),
+            _ => return,
+        };
+
+        if let Some(score) = self.correlation_score(&zone.zone_name, &patterns) {
+            if score <= Self::CORRELATION_ANOMALY_THRESHOLD {
+                conversation.interaction_type = InteractionType::EmergencyResponse;
+            }
+        }
+    }
+
+    // Refits `EffectivenessModel` over the full conversation history. Called
+    // on every evolution milestone rather than every conversation, since
+    // refitting scans all labeled history and a GBDT's boosting rounds
+    // aren't cheap enough to redo on every turn.
+    fn retrain_effectiveness_model(&self) {
+        let history = self.conversation_history.lock().unwrap();
+        let zone_patterns = self.zone_patterns.lock().unwrap();
+        let model = EffectivenessModel::fit(&history, &zone_patterns);
+        drop(history);
+        drop(zone_patterns);
+
+        if let Some(model) = model {
+            *self.effectiveness_model.lock().unwrap() = Some(model);
+        }
+    }
+
+    /// Serializes the full engine state to `<data_dir>/luna_evolution_state.json`,
+    /// so `IntelligenceLevel` evolution and everything that fed it survive a
+    /// restart.
+    pub async fn persist(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let snapshot = LunaStateSnapshot {
+            personality: Some(self.personality.lock().unwrap().clone()),
+            evolution_metrics: Some(self.evolution_metrics.lock().unwrap().clone()),
+            conversation_history: self.conversation_history.lock().unwrap().clone(),
+            zone_patterns: self.zone_patterns.lock().unwrap().clone(),
+            biocore_effectiveness: self.biocore_effectiveness.lock().unwrap().clone(),
+            learning_cache: self.learning_cache.lock().unwrap().clone(),
+            frequency_optimizer: self.frequency_optimizer.lock().unwrap().clone(),
+            evolutionary_tuner: self.evolutionary_tuner.lock().unwrap().clone(),
+            zone_memory: self.zone_memory.lock().unwrap().clone(),
+            self_organizing_map: self.self_organizing_map.lock().unwrap().clone(),
+            state_estimates: self.state_estimates.lock().unwrap().clone(),
+            effectiveness_model: self.effectiveness_model.lock().unwrap().clone(),
+            pattern_templates: self.pattern_templates.lock().unwrap().clone(),
+        };
+
+        tokio::fs::create_dir_all(&self.data_dir).await?;
+        let json = serde_json::to_string(&snapshot)?;
+        tokio::fs::write(self.data_dir.join(STATE_FILE_NAME), json).await?;
         Ok(())
     }
 }
@@ -820,4 +2694,346 @@ mod tests {
         assert_eq!(personality.total_interactions, 1);
         assert!(matches!(personality.intelligence_level, IntelligenceLevel::Beginner));
     }
+
+    #[test]
+    fn pearson_correlation_of_identical_series_is_one() {
+        let a = vec![0.1, 0.4, 0.2, 0.8, 0.5, 0.3];
+        assert!((pearson_correlation(&a, &a) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pearson_correlation_of_inverted_series_is_negative_one() {
+        let a = vec![0.1, 0.4, 0.2, 0.8, 0.5];
+        let b: Vec<f64> = a.iter().map(|x| -x).collect();
+        assert!((pearson_correlation(&a, &b) - (-1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pearson_correlation_is_zero_for_constant_series() {
+        let a = vec![0.5, 0.5, 0.5, 0.5];
+        let b = vec![0.1, 0.4, 0.2, 0.8];
+        assert_eq!(pearson_correlation(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn pearson_correlation_is_zero_with_insufficient_overlap() {
+        assert_eq!(pearson_correlation(&[1.0], &[2.0]), 0.0);
+    }
+
+    #[test]
+    fn dominant_frequency_finds_injected_periodic_signal() {
+        // A clean period-8 sine over a 64-sample window should land its
+        // energy in bin 8 (64 / 8).
+        let samples: Vec<f64> = (0..64)
+            .map(|i| (2.0 * std::f64::consts::PI * i as f64 / 8.0).sin())
+            .collect();
+
+        let peak = dominant_frequency(&samples).expect("clean periodic signal should yield a peak");
+        assert_eq!(peak.bin, 8);
+        assert!(peak.relative_power > 0.9);
+    }
+
+    #[test]
+    fn dominant_frequency_is_none_for_too_short_input() {
+        assert!(dominant_frequency(&[0.1, 0.2, 0.3]).is_none());
+    }
+
+    #[test]
+    fn retrievability_is_one_at_zero_elapsed_time() {
+        assert_eq!(retrievability(1.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn retrievability_decays_as_time_elapses() {
+        let soon = retrievability(1.0, 1.0);
+        let later = retrievability(1.0, 30.0);
+        assert!(soon > later);
+        assert!(later > 0.0);
+    }
+
+    #[test]
+    fn retrievability_decays_slower_with_higher_stability() {
+        let low_stability = retrievability(1.0, 10.0);
+        let high_stability = retrievability(10.0, 10.0);
+        assert!(high_stability > low_stability);
+    }
+
+    #[test]
+    fn memory_item_observe_grows_stability_on_confirmation() {
+        let now = chrono::Utc::now();
+        let mut item = MemoryItem {
+            value: 0.5,
+            timestamp: now - chrono::Duration::days(5),
+            stability: INITIAL_STABILITY,
+        };
+
+        item.observe(0.52, now);
+        assert!(item.stability > INITIAL_STABILITY);
+    }
+
+    #[test]
+    fn memory_item_observe_shrinks_stability_on_contradiction() {
+        let now = chrono::Utc::now();
+        let mut item = MemoryItem {
+            value: 0.5,
+            timestamp: now - chrono::Duration::days(5),
+            stability: INITIAL_STABILITY,
+        };
+
+        item.observe(0.95, now);
+        assert!(item.stability <= INITIAL_STABILITY);
+        assert!(item.stability >= MIN_STABILITY);
+    }
+
+    #[test]
+    fn effectiveness_model_fit_reduces_squared_error_vs_baseline() {
+        let zone = ZoneContext {
+            zone_name: "Downtown".to_string(),
+            activity_level: 0.5,
+            stress_level: 0.5,
+            population_density: 0.5,
+            primary_function: "Commercial".to_string(),
+        };
+        let effect = BioCoreEffect {
+            plant_name: "Ashwagandha".to_string(),
+            drug_name: "DrugA".to_string(),
+            synergy_score: 0.0,
+            parameter: ZoneParameter::Stress,
+            delta: -0.1,
+            duration_minutes: 10,
+            curve: EffectCurve::Linear,
+        };
+
+        // effectiveness_score tracks synergy_score exactly, so a model that
+        // learns anything at all should beat predicting the flat mean.
+        let conversations: Vec<Conversation> = (0..40)
+            .map(|i| {
+                let synergy = i as f64 / 40.0;
+                let mut applied = effect.clone();
+                applied.synergy_score = synergy;
+                Conversation {
+                    id: format!("c{i}"),
+                    timestamp: chrono::Utc::now(),
+                    user_message: String::new(),
+                    luna_response: String::new(),
+                    interaction_type: InteractionType::BioCoreRecommendation,
+                    zone_context: Some(zone.clone()),
+                    biocore_applied: Some(applied),
+                    effectiveness_score: synergy,
+                    learning_weight: 0.0,
+                }
+            })
+            .collect();
+
+        let zone_patterns = HashMap::new();
+        let model = EffectivenessModel::fit(&conversations, &zone_patterns)
+            .expect("40 labeled conversations clears MIN_LABELED_CONVERSATIONS");
+
+        let targets: Vec<f64> = conversations.iter().map(|c| c.effectiveness_score).collect();
+        let base_value = targets.iter().sum::<f64>() / targets.len() as f64;
+        let baseline_sse: f64 = targets.iter().map(|t| (t - base_value).powi(2)).sum();
+
+        let model_sse: f64 = conversations
+            .iter()
+            .map(|c| {
+                let zone = c.zone_context.as_ref().unwrap();
+                let applied = c.biocore_applied.as_ref().unwrap();
+                let features = effectiveness_features(zone, applied, c.timestamp, 0.0, 0.0);
+                (c.effectiveness_score - model.predict(&features)).powi(2)
+            })
+            .sum();
+
+        assert!(model_sse < baseline_sse);
+    }
+
+    #[test]
+    fn promote_and_breed_promotes_the_highest_fitness_genome() {
+        let mut tuner = EvolutionaryTuner::default();
+        for (i, genome) in tuner.population.iter_mut().enumerate() {
+            genome.fitness = Some(i as f64 * 0.1);
+        }
+        let expected_fitness = tuner.population.last().unwrap().fitness;
+
+        tuner.promote_and_breed();
+
+        assert_eq!(tuner.live_genome().fitness, expected_fitness);
+    }
+
+    #[test]
+    fn observe_moves_bmu_prototype_toward_the_input() {
+        let mut som = SelfOrganizingMap::default();
+        let origin = [0.0, 0.0, 0.0, 0.0];
+        som.observe("ZoneA", origin, 0.5);
+
+        let target = [1.0, 1.0, 1.0, 1.0];
+        let before = som.nodes[0].prototype;
+        som.observe("ZoneA", target, 0.5);
+        let after = som.nodes[0].prototype;
+
+        for i in 0..SOM_FEATURE_COUNT {
+            assert!(
+                (after[i] - target[i]).abs() < (before[i] - target[i]).abs(),
+                "feature {i} should move closer to the input: before={before:?} after={after:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn q_value_update_moves_toward_observed_reward() {
+        let engine = LunaEvolutionEngine::new_with_data_dir("test_data_q_value_update");
+        let zone = ZoneContext {
+            zone_name: "Q".to_string(),
+            activity_level: 0.5,
+            stress_level: 0.5,
+            population_density: 0.5,
+            primary_function: "Test".to_string(),
+        };
+        let state = ZoneState::from_context(&zone);
+        let action = BioCoreAction { plant_name: "Ashwagandha".to_string(), drug_name: "DrugA".to_string() };
+
+        engine.update_q_value(&state, &action, 1.0, 0.5);
+        let first = *engine.state_estimates.lock().unwrap().get(&state).unwrap().get(&action).unwrap();
+        engine.update_q_value(&state, &action, 1.0, 0.5);
+        let second = *engine.state_estimates.lock().unwrap().get(&state).unwrap().get(&action).unwrap();
+
+        assert!(second > first, "repeated positive reward should keep raising Q(s,a)");
+    }
+
+    #[test]
+    fn threshold_detector_flags_a_large_deviation() {
+        let detector = ThresholdDetector::default();
+        // The spike counts toward the mean/sigma it is scored against, so
+        // one outlier among n samples scores at most sqrt(n - 1); 20
+        // samples leave room to clear k = 3.
+        let mut samples = vec![0.5; 19];
+        samples.push(50.0);
+
+        let anomaly = detector.detect("Zone", &samples).expect("large spike should flag");
+
+        assert_eq!(anomaly.detector, "threshold");
+        assert!(anomaly.severity > detector.k);
+    }
+
+    #[test]
+    fn threshold_detector_ignores_stable_series() {
+        let detector = ThresholdDetector::default();
+        let samples = vec![0.5, 0.51, 0.49, 0.5, 0.5];
+        assert!(detector.detect("Zone", &samples).is_none());
+    }
+
+    #[test]
+    fn pattern_detector_matches_its_known_template() {
+        let detector = PatternDetector::default();
+        let samples = vec![0.2, 0.35, 0.5, 0.7, 0.95];
+
+        let anomaly = detector.detect("Zone", &samples).expect("exact template match should flag");
+
+        assert_eq!(anomaly.detector, "pattern");
+        assert!(anomaly.severity > detector.confidence);
+    }
+
+    #[test]
+    fn pattern_detector_ignores_unrelated_shape() {
+        let detector = PatternDetector::default();
+        let samples = vec![0.9, 0.3, 0.6, 0.1, 0.4];
+        assert!(detector.detect("Zone", &samples).is_none());
+    }
+
+    #[test]
+    fn luna_state_snapshot_round_trips_through_json() {
+        let mut snapshot = LunaStateSnapshot::default();
+        snapshot.learning_cache.insert("ZoneAnalysis".to_string(), 0.9);
+        snapshot.zone_patterns.insert("Downtown".to_string(), vec![0.1, 0.2, 0.3]);
+        snapshot.evolutionary_tuner.record(0.7);
+
+        let json = serde_json::to_string(&snapshot).expect("snapshot should serialize");
+        let restored: LunaStateSnapshot = serde_json::from_str(&json).expect("snapshot should deserialize");
+
+        assert_eq!(restored.learning_cache.get("ZoneAnalysis"), Some(&0.9));
+        assert_eq!(restored.zone_patterns.get("Downtown"), Some(&vec![0.1, 0.2, 0.3]));
+        assert_eq!(restored.evolutionary_tuner.trial_count, snapshot.evolutionary_tuner.trial_count);
+    }
+
+    #[tokio::test]
+    async fn maybe_alert_critical_zone_debounces_repeat_alerts() {
+        let mut engine = LunaEvolutionEngine::new_with_data_dir("test_data_alerting_debounce");
+        engine.alerting = Some(AlertingConfig {
+            transport: AlertTransport::Webhook { endpoint: "http://127.0.0.1:0/alert".to_string() },
+            min_interval: Duration::from_secs(300),
+        });
+        let zone = ZoneContext {
+            zone_name: "Critical".to_string(),
+            activity_level: 0.9,
+            stress_level: 0.9,
+            population_density: 0.5,
+            primary_function: "Test".to_string(),
+        };
+        let effect = biocore_catalog()[0].clone();
+
+        engine.maybe_alert_critical_zone(&zone, &effect, &InteractionType::EmergencyResponse);
+        let first_alerted_at =
+            *engine.last_alerted.lock().unwrap().get("Critical").expect("critical zone should be recorded");
+
+        engine.maybe_alert_critical_zone(&zone, &effect, &InteractionType::EmergencyResponse);
+        let second_alerted_at = *engine.last_alerted.lock().unwrap().get("Critical").unwrap();
+
+        assert_eq!(first_alerted_at, second_alerted_at, "repeat alert within min_interval should be debounced");
+    }
+
+    #[tokio::test]
+    async fn maybe_alert_critical_zone_ignores_non_critical_zone() {
+        let mut engine = LunaEvolutionEngine::new_with_data_dir("test_data_alerting_non_critical");
+        engine.alerting = Some(AlertingConfig {
+            transport: AlertTransport::Webhook { endpoint: "http://127.0.0.1:0/alert".to_string() },
+            min_interval: Duration::from_secs(300),
+        });
+        let zone = ZoneContext {
+            zone_name: "Calm".to_string(),
+            activity_level: 0.3,
+            stress_level: 0.2,
+            population_density: 0.5,
+            primary_function: "Test".to_string(),
+        };
+        let effect = biocore_catalog()[0].clone();
+
+        engine.maybe_alert_critical_zone(&zone, &effect, &InteractionType::ZoneAnalysis);
+
+        assert!(engine.last_alerted.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_detection_cycle_records_a_conversation_for_an_anomalous_zone() {
+        let engine = LunaEvolutionEngine::new_with_data_dir("test_data_detection_cycle");
+        // Exactly PatternDetector's default template, so this flags
+        // deterministically regardless of ThresholdDetector's z-score.
+        engine
+            .zone_patterns
+            .lock()
+            .unwrap()
+            .insert("Flagged".to_string(), vec![0.2, 0.35, 0.5, 0.7, 0.95]);
+
+        engine.run_detection_cycle().await;
+
+        let history = engine.conversation_history.lock().unwrap();
+        assert_eq!(history.len(), 1);
+        assert!(matches!(history[0].interaction_type, InteractionType::EmergencyResponse));
+        assert_eq!(engine.evolution_metrics.lock().unwrap().optimizations_applied, 1);
+    }
+
+    #[test]
+    fn frequency_optimizer_step_learns_nonzero_weights_from_reward() {
+        let mut optimizer = FrequencyOptimizer::default();
+        let features = [0.5, 0.5, 0.5, 0.5, 1.0];
+
+        // The first step only has an action to pick, nothing pending yet to
+        // TD-update; every step after completes the previous one's update.
+        for _ in 0..20 {
+            optimizer.step("Zone", features, 1.0, 0.5);
+        }
+
+        let learned_any_weight = FrequencyAction::ALL
+            .iter()
+            .any(|&a| optimizer.weights(a).iter().any(|w| w.abs() > f64::EPSILON));
+        assert!(learned_any_weight, "sustained positive reward should move at least one action's weights");
+    }
 }
@@ -1,11 +1,58 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio::time::Duration;
 use warp::Filter;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 struct Zone {
     id: usize,
     activity: f32, // 0.0 calm -> 1.0 emergent
+    charge_rate: f32,    // max upward move per tick
+    discharge_rate: f32, // max downward move per tick
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ConfigUpdate {
+    tick_ms: Option<u64>,
+    charge_rate: Option<f32>,
+    discharge_rate: Option<f32>,
+    kp: Option<f32>,
+    ki: Option<f32>,
+    kd: Option<f32>,
+    integral_max: Option<f32>,
+    max_concentration: Option<f32>,
+    concentration_mode: Option<ConcentrationMode>,
+}
+
+// "warn" only records why concentration is high; "active" also applies
+// corrective damping to the over-represented zones.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum ConcentrationMode {
+    Warn,
+    Active,
+}
+
+// Logged whenever a zone's activity share exceeds its fair share while the
+// city is over the concentration threshold, whether or not damping fired.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ConcentrationAction {
+    tick: u64,
+    zone_id: usize,
+    share: f32,
+    excess_share: f32,
+    damping: f32,
+    mode: ConcentrationMode,
+}
+
+// Per-zone PID accumulators, kept alongside `ema` rather than on `Zone` itself
+// since they're controller state, not part of the zone's observable data.
+#[derive(Clone, Debug, Default)]
+struct PidState {
+    integral: f32,
+    prev_error: f32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -16,69 +63,575 @@ struct BioCoreInput {
     synergy: f32,
 }
 
-#[derive(Clone)]
+#[derive(Deserialize, Debug)]
+struct BioCoreBatch {
+    inputs: Vec<BioCoreInput>,
+    budget: f32,
+}
+
+#[derive(Serialize, Debug)]
+struct BioCoreBatchVerdict {
+    committed: bool,
+    total_adjustment: f32,
+    tripped_at: Option<usize>,
+    reason: Option<String>,
+}
+
+// Pushed to `/subscribe` clients whenever a zone's derived state label changes.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ZoneDelta {
+    id: usize,
+    activity: f32,
+    state: String,
+}
+
+const DEFAULT_INTEGRAL_MAX: f32 = 1.0;
+
+// Monitors zone dynamics and raises a `WardEvent` when a sustained bad
+// condition is detected, evaluated once at the end of every tick.
+trait Ward: Send {
+    fn name(&self) -> &str;
+    fn check(&mut self, state: &CityState) -> Option<WardEvent>;
+    fn set_threshold(&mut self, value: f32);
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct WardEvent {
+    ward: String,
+    zone_ids: Vec<usize>,
+    tick: u64,
+    severity: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct WardConfig {
+    name: String,
+    threshold: f32,
+}
+
+// Fires when every zone has been EMERGENT for `threshold` consecutive ticks.
+struct SustainedEmergentWard {
+    threshold: u32,
+    streak: u32,
+}
+
+impl Ward for SustainedEmergentWard {
+    fn name(&self) -> &str {
+        "sustained_emergent"
+    }
+
+    fn check(&mut self, state: &CityState) -> Option<WardEvent> {
+        let all_emergent = state.zones.iter().all(|z| CityState::zone_state(z.activity) == "EMERGENT");
+        if all_emergent {
+            self.streak += 1;
+        } else {
+            self.streak = 0;
+        }
+
+        if self.streak >= self.threshold {
+            Some(WardEvent {
+                ward: self.name().to_string(),
+                zone_ids: state.zones.iter().map(|z| z.id).collect(),
+                tick: state.tick_count,
+                severity: "critical".to_string(),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn set_threshold(&mut self, value: f32) {
+        self.threshold = value.max(1.0) as u32;
+    }
+}
+
+// Fires when a zone's activity has been clamped at 0.0 or 1.0 for `threshold` consecutive ticks.
+struct StuckAtBoundWard {
+    threshold: u32,
+    streaks: HashMap<usize, u32>,
+}
+
+impl Ward for StuckAtBoundWard {
+    fn name(&self) -> &str {
+        "stuck_at_bound"
+    }
+
+    fn check(&mut self, state: &CityState) -> Option<WardEvent> {
+        let mut stuck_zones = Vec::new();
+        for zone in &state.zones {
+            let streak = self.streaks.entry(zone.id).or_insert(0);
+            if zone.activity <= 0.0 || zone.activity >= 1.0 {
+                *streak += 1;
+            } else {
+                *streak = 0;
+            }
+            if *streak >= self.threshold {
+                stuck_zones.push(zone.id);
+            }
+        }
+
+        if stuck_zones.is_empty() {
+            None
+        } else {
+            Some(WardEvent {
+                ward: self.name().to_string(),
+                zone_ids: stuck_zones,
+                tick: state.tick_count,
+                severity: "warning".to_string(),
+            })
+        }
+    }
+
+    fn set_threshold(&mut self, value: f32) {
+        self.threshold = value.max(1.0) as u32;
+    }
+}
+
+// Fires when mean activity drifts more than `tolerance` away from target.
+struct DriftWard {
+    tolerance: f32,
+}
+
+impl Ward for DriftWard {
+    fn name(&self) -> &str {
+        "target_drift"
+    }
+
+    fn check(&mut self, state: &CityState) -> Option<WardEvent> {
+        let mean = state.zones.iter().map(|z| z.activity).sum::<f32>() / state.zones.len() as f32;
+        if (mean - state.target).abs() > self.tolerance {
+            Some(WardEvent {
+                ward: self.name().to_string(),
+                zone_ids: state.zones.iter().map(|z| z.id).collect(),
+                tick: state.tick_count,
+                severity: "warning".to_string(),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn set_threshold(&mut self, value: f32) {
+        self.tolerance = value;
+    }
+}
+
+// Samples a single numeric fact out of `CityState` on every tick; the result
+// is stored in a per-measurement time series so the simulation stays
+// observable without changing the engine itself.
+trait Measurement: Send + Sync {
+    fn name(&self) -> &str;
+    fn sample(&self, state: &CityState) -> serde_json::Value;
+}
+
+struct PerZoneActivityMeasurement;
+
+impl Measurement for PerZoneActivityMeasurement {
+    fn name(&self) -> &str {
+        "per_zone_activity"
+    }
+
+    fn sample(&self, state: &CityState) -> serde_json::Value {
+        let zones: Vec<_> = state.zones.iter()
+            .map(|z| serde_json::json!({ "id": z.id, "activity": z.activity }))
+            .collect();
+        serde_json::json!(zones)
+    }
+}
+
+struct MeanActivityMeasurement;
+
+impl Measurement for MeanActivityMeasurement {
+    fn name(&self) -> &str {
+        "mean_activity"
+    }
+
+    fn sample(&self, state: &CityState) -> serde_json::Value {
+        let mean = state.zones.iter().map(|z| z.activity).sum::<f32>() / state.zones.len() as f32;
+        serde_json::json!(mean)
+    }
+}
+
+struct TargetErrorMeasurement;
+
+impl Measurement for TargetErrorMeasurement {
+    fn name(&self) -> &str {
+        "target_error"
+    }
+
+    fn sample(&self, state: &CityState) -> serde_json::Value {
+        let mean = state.zones.iter().map(|z| z.activity).sum::<f32>() / state.zones.len() as f32;
+        serde_json::json!(mean - state.target)
+    }
+}
+
+// Total absolute deviation from target across all zones - a cheap proxy for
+// how much corrective "energy" the controllers are currently spending.
+struct EnergyMeasurement;
+
+impl Measurement for EnergyMeasurement {
+    fn name(&self) -> &str {
+        "energy"
+    }
+
+    fn sample(&self, state: &CityState) -> serde_json::Value {
+        let energy: f32 = state.zones.iter().map(|z| (z.activity - state.target).abs()).sum();
+        serde_json::json!(energy)
+    }
+}
+
 struct CityState {
     zones: Vec<Zone>,
     target: f32,
     eta: f32,
     ema: Vec<f32>,
+    pid: Vec<PidState>,
+    kp: f32,
+    ki: f32,
+    kd: f32,
+    integral_max: f32,
+    dt: f32,
+    tick_ms: u64,
+    tick_count: u64,
+    wards: Vec<Box<dyn Ward>>,
+    ward_events: VecDeque<WardEvent>,
+    max_concentration: f32,
+    concentration_mode: ConcentrationMode,
+    concentration: f32,
+    concentration_actions: VecDeque<ConcentrationAction>,
+    measurements: Vec<Arc<dyn Measurement>>,
+    measurement_history: HashMap<String, Vec<(u64, serde_json::Value)>>,
+    last_state_labels: Vec<String>,
+    tx: broadcast::Sender<Vec<ZoneDelta>>,
 }
 
 impl CityState {
+    const WARD_EVENT_CAPACITY: usize = 200;
+    const CONCENTRATION_ACTION_CAPACITY: usize = 200;
+
     fn new(zones_count: usize, target: f32, eta: f32) -> Self {
         let zones: Vec<Zone> = (0..zones_count)
-            .map(|i| Zone { 
-                id: i, 
-                activity: rand::random::<f32>() 
+            .map(|i| Zone {
+                id: i,
+                activity: rand::random::<f32>(),
+                charge_rate: 0.05,
+                discharge_rate: 0.05,
             })
             .collect();
-        
+
         let ema = zones.iter().map(|z| z.activity).collect();
-        
+        let pid = vec![PidState::default(); zones.len()];
+        let last_state_labels = zones.iter().map(|z| Self::zone_state(z.activity).to_string()).collect();
+        let (tx, _rx) = broadcast::channel(64);
+        let concentration = 1.0 / zones.len() as f32;
+
+        let wards: Vec<Box<dyn Ward>> = vec![
+            Box::new(SustainedEmergentWard { threshold: 5, streak: 0 }),
+            Box::new(StuckAtBoundWard { threshold: 5, streaks: HashMap::new() }),
+            Box::new(DriftWard { tolerance: 0.3 }),
+        ];
+
+        let measurements: Vec<Arc<dyn Measurement>> = vec![
+            Arc::new(PerZoneActivityMeasurement),
+            Arc::new(MeanActivityMeasurement),
+            Arc::new(TargetErrorMeasurement),
+            Arc::new(EnergyMeasurement),
+        ];
+
         Self {
             zones,
             target,
             eta,
             ema,
+            pid,
+            kp: eta, // defaults to the old proportional-only behavior
+            ki: 0.0,
+            kd: 0.0,
+            integral_max: DEFAULT_INTEGRAL_MAX,
+            dt: 1.0,
+            tick_ms: 1000,
+            tick_count: 0,
+            wards,
+            ward_events: VecDeque::new(),
+            max_concentration: 0.5,
+            concentration_mode: ConcentrationMode::Warn,
+            concentration,
+            concentration_actions: VecDeque::new(),
+            measurements,
+            measurement_history: HashMap::new(),
+            last_state_labels,
+            tx,
         }
     }
-    
+
     fn homeostatic_update(&mut self) {
         for (i, zone) in self.zones.iter_mut().enumerate() {
             // Update EMA with current activity
             self.ema[i] = 0.97 * self.ema[i] + 0.03 * zone.activity;
-            
-            // Compute error and adjustment
+
+            // PID control against the smoothed activity.
             let error = self.target - self.ema[i];
-            let adjustment = self.eta * error;
-            
+            let pid = &mut self.pid[i];
+
+            let was_saturated = zone.activity <= 0.0 || zone.activity >= 1.0;
+            if !was_saturated {
+                pid.integral = (pid.integral + error * self.dt).clamp(-self.integral_max, self.integral_max);
+            }
+            let derivative = (error - pid.prev_error) / self.dt;
+            pid.prev_error = error;
+
+            let mut adjustment = self.kp * error + self.ki * pid.integral + self.kd * derivative;
+
+            // Bound the step by the zone's charge/discharge rate so the
+            // controller output can't overshoot regardless of error magnitude.
+            if adjustment > 0.0 {
+                adjustment = adjustment.min(zone.charge_rate);
+            } else {
+                adjustment = adjustment.max(-zone.discharge_rate);
+            }
+
             // Apply adjustment with bounds checking
             zone.activity += adjustment;
             zone.activity = zone.activity.clamp(0.0, 1.0);
         }
+
+        self.tick_count += 1;
+        self.evaluate_wards();
+        self.evaluate_concentration();
+        self.sample_measurements();
+        self.broadcast_changed_zones();
     }
-    
-    fn apply_biocore_effect(&mut self, input: BioCoreInput) {
-        if let Some(zone) = self.zones.get_mut(input.zone) {
-            // Apply BioCore synergy effect
-            // High synergy reduces overstimulation
-            let effect = if zone.activity > 0.7 {
-                // Dampen overstimulated zones
-                -0.05 * input.synergy
-            } else if zone.activity < 0.4 {
-                // Slightly activate calm zones
-                0.03 * input.synergy
+
+    // Computes a Herfindahl-style concentration index (sum of squared activity
+    // shares) across zones. When it exceeds `max_concentration`, the zones
+    // contributing more than their fair share are flagged - and, in active
+    // mode, dampened proportionally to how far over their fair share they are.
+    fn evaluate_concentration(&mut self) {
+        let total: f32 = self.zones.iter().map(|z| z.activity).sum();
+        let n = self.zones.len() as f32;
+
+        if total <= 0.0 {
+            self.concentration = 1.0 / n;
+            return;
+        }
+
+        let shares: Vec<f32> = self.zones.iter().map(|z| z.activity / total).collect();
+        self.concentration = shares.iter().map(|s| s * s).sum();
+
+        if self.concentration <= self.max_concentration {
+            return;
+        }
+
+        let fair_share = 1.0 / n;
+        let tick = self.tick_count;
+        let mode = self.concentration_mode.clone();
+
+        for (i, zone) in self.zones.iter_mut().enumerate() {
+            let share = shares[i];
+            if share <= fair_share {
+                continue;
+            }
+            let excess_share = share - fair_share;
+
+            let damping = if mode == ConcentrationMode::Active {
+                let d = excess_share * zone.discharge_rate;
+                zone.activity = (zone.activity - d).clamp(0.0, 1.0);
+                d
             } else {
-                // Minimal effect on balanced zones
-                0.01 * (input.synergy - 0.5)
+                0.0
             };
-            
+
+            self.concentration_actions.push_back(ConcentrationAction {
+                tick,
+                zone_id: zone.id,
+                share,
+                excess_share,
+                damping,
+                mode: mode.clone(),
+            });
+            if self.concentration_actions.len() > Self::CONCENTRATION_ACTION_CAPACITY {
+                self.concentration_actions.pop_front();
+            }
+        }
+    }
+
+    fn sample_measurements(&mut self) {
+        let tick = self.tick_count;
+        let samples: Vec<(String, serde_json::Value)> = self.measurements.iter()
+            .map(|m| (m.name().to_string(), m.sample(self)))
+            .collect();
+
+        for (name, value) in samples {
+            self.measurement_history.entry(name).or_insert_with(Vec::new).push((tick, value));
+        }
+    }
+
+    // Allows embedders to add a measurement beyond the seeded built-ins.
+    fn register_measurement(&mut self, measurement: Arc<dyn Measurement>) {
+        self.measurements.push(measurement);
+    }
+
+    fn measurements_since(&self, since: u64) -> HashMap<String, Vec<(u64, serde_json::Value)>> {
+        self.measurement_history.iter()
+            .map(|(name, series)| {
+                let filtered: Vec<_> = series.iter().filter(|(tick, _)| *tick > since).cloned().collect();
+                (name.clone(), filtered)
+            })
+            .collect()
+    }
+
+    // Flushes the full accumulated history to NDJSON, one line per
+    // (measurement, tick, value) sample, for offline analysis.
+    fn flush_measurements(&self, path: &str) -> std::io::Result<usize> {
+        use std::io::Write;
+
+        let mut file = std::fs::File::create(path)?;
+        let mut count = 0;
+        for (name, series) in &self.measurement_history {
+            for (tick, value) in series {
+                let line = serde_json::json!({ "measurement": name, "tick": tick, "value": value });
+                writeln!(file, "{}", line)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    fn evaluate_wards(&mut self) {
+        let mut wards = std::mem::take(&mut self.wards);
+        for ward in wards.iter_mut() {
+            if let Some(event) = ward.check(self) {
+                self.ward_events.push_back(event);
+                if self.ward_events.len() > Self::WARD_EVENT_CAPACITY {
+                    self.ward_events.pop_front();
+                }
+            }
+        }
+        self.wards = wards;
+    }
+
+    fn register_ward_threshold(&mut self, config: WardConfig) -> bool {
+        for ward in self.wards.iter_mut() {
+            if ward.name() == config.name {
+                ward.set_threshold(config.threshold);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn configure(&mut self, update: ConfigUpdate) {
+        if let Some(tick_ms) = update.tick_ms {
+            self.tick_ms = tick_ms;
+        }
+        if let Some(kp) = update.kp {
+            self.kp = kp;
+        }
+        if let Some(ki) = update.ki {
+            self.ki = ki;
+        }
+        if let Some(kd) = update.kd {
+            self.kd = kd;
+        }
+        if let Some(integral_max) = update.integral_max {
+            self.integral_max = integral_max;
+        }
+        if let Some(max_concentration) = update.max_concentration {
+            self.max_concentration = max_concentration;
+        }
+        if let Some(concentration_mode) = update.concentration_mode {
+            self.concentration_mode = concentration_mode;
+        }
+        for zone in self.zones.iter_mut() {
+            if let Some(charge_rate) = update.charge_rate {
+                zone.charge_rate = charge_rate;
+            }
+            if let Some(discharge_rate) = update.discharge_rate {
+                zone.discharge_rate = discharge_rate;
+            }
+        }
+    }
+
+    fn apply_biocore_effect(&mut self, input: BioCoreInput) {
+        if let Some(zone) = self.zones.get_mut(input.zone) {
+            let effect = Self::biocore_delta(zone, &input);
             zone.activity += effect;
             zone.activity = zone.activity.clamp(0.0, 1.0);
         }
+
+        self.broadcast_changed_zones();
     }
-    
-    fn get_zone_state(&self, activity: f32) -> &'static str {
+
+    // BioCore synergy effect - high synergy reduces overstimulation.
+    fn biocore_delta(zone: &Zone, input: &BioCoreInput) -> f32 {
+        if zone.activity > 0.7 {
+            // Dampen overstimulated zones
+            -0.05 * input.synergy
+        } else if zone.activity < 0.4 {
+            // Slightly activate calm zones
+            0.03 * input.synergy
+        } else {
+            // Minimal effect on balanced zones
+            0.01 * (input.synergy - 0.5)
+        }
+    }
+
+    // Applies a batch of BioCore inputs as an all-or-nothing transaction:
+    // speculatively apply every input to a cloned zone list, and only commit
+    // if the summed absolute adjustment stays within `budget` and no zone
+    // jumps directly from CALM to EMERGENT in the same batch.
+    fn apply_biocore_batch(&mut self, batch: BioCoreBatch) -> BioCoreBatchVerdict {
+        let mut speculative = self.zones.clone();
+        let mut total_adjustment = 0.0f32;
+
+        for (i, input) in batch.inputs.iter().enumerate() {
+            let Some(zone) = speculative.get_mut(input.zone) else {
+                return BioCoreBatchVerdict {
+                    committed: false,
+                    total_adjustment,
+                    tripped_at: Some(i),
+                    reason: Some(format!("zone {} not found", input.zone)),
+                };
+            };
+
+            let before_state = Self::zone_state(zone.activity);
+            let delta = Self::biocore_delta(zone, input);
+            zone.activity = (zone.activity + delta).clamp(0.0, 1.0);
+            let after_state = Self::zone_state(zone.activity);
+
+            total_adjustment += delta.abs();
+
+            if total_adjustment > batch.budget {
+                return BioCoreBatchVerdict {
+                    committed: false,
+                    total_adjustment,
+                    tripped_at: Some(i),
+                    reason: Some("synergy budget exceeded".to_string()),
+                };
+            }
+
+            if before_state == "CALM" && after_state == "EMERGENT" {
+                return BioCoreBatchVerdict {
+                    committed: false,
+                    total_adjustment,
+                    tripped_at: Some(i),
+                    reason: Some(format!("zone {} jumped CALM -> EMERGENT in one batch", input.zone)),
+                };
+            }
+        }
+
+        self.zones = speculative;
+        self.broadcast_changed_zones();
+
+        BioCoreBatchVerdict {
+            committed: true,
+            total_adjustment,
+            tripped_at: None,
+            reason: None,
+        }
+    }
+
+    fn zone_state(activity: f32) -> &'static str {
         if activity < 0.4 {
             "CALM"
         } else if activity < 0.7 {
@@ -87,6 +640,47 @@ impl CityState {
             "EMERGENT"
         }
     }
+
+    fn get_zone_state(&self, activity: f32) -> &'static str {
+        Self::zone_state(activity)
+    }
+
+    // Only ships zones whose state label actually flipped since the last frame,
+    // so idle clients don't pay for every tiny activity wiggle.
+    fn broadcast_changed_zones(&mut self) {
+        if self.tx.receiver_count() == 0 {
+            // Still keep last_state_labels in sync even with no subscribers.
+            for (i, zone) in self.zones.iter().enumerate() {
+                self.last_state_labels[i] = Self::zone_state(zone.activity).to_string();
+            }
+            return;
+        }
+
+        let mut deltas = Vec::new();
+        for (i, zone) in self.zones.iter().enumerate() {
+            let state = Self::zone_state(zone.activity).to_string();
+            if state != self.last_state_labels[i] {
+                deltas.push(ZoneDelta {
+                    id: zone.id,
+                    activity: zone.activity,
+                    state: state.clone(),
+                });
+                self.last_state_labels[i] = state;
+            }
+        }
+
+        if !deltas.is_empty() {
+            let _ = self.tx.send(deltas);
+        }
+    }
+
+    fn snapshot(&self) -> Vec<ZoneDelta> {
+        self.zones.iter().map(|z| ZoneDelta {
+            id: z.id,
+            activity: z.activity,
+            state: Self::zone_state(z.activity).to_string(),
+        }).collect()
+    }
 }
 
 #[tokio::main]
@@ -94,25 +688,38 @@ async fn main() {
     // Initialize city state with 5 zones
     let state = Arc::new(Mutex::new(CityState::new(5, 0.5, 0.02)));
 
+    // Background tick loop - the city evolves continuously instead of only
+    // advancing when a client happens to poll GET /state.
+    let tick_state = Arc::clone(&state);
+    tokio::spawn(async move {
+        loop {
+            let tick_ms = tick_state.lock().unwrap().tick_ms;
+            tokio::time::sleep(Duration::from_millis(tick_ms)).await;
+            tick_state.lock().unwrap().homeostatic_update();
+        }
+    });
+
     let state_filter = warp::any().map(move || state.clone());
 
-    // GET /state - Get current city state and update homeostasis
+    // GET /state - pure read of current city state, no simulation step
     let state_route = warp::path("state")
         .and(warp::get())
         .and(state_filter.clone())
         .map(|state: Arc<Mutex<CityState>>| {
-            let mut s = state.lock().unwrap();
-            s.homeostatic_update();
-            
-            let response: Vec<_> = s.zones.iter().map(|z| {
+            let s = state.lock().unwrap();
+
+            let zones: Vec<_> = s.zones.iter().map(|z| {
                 serde_json::json!({
                     "id": z.id,
                     "activity": z.activity,
                     "state": s.get_zone_state(z.activity)
                 })
             }).collect();
-            
-            warp::reply::json(&response)
+
+            warp::reply::json(&serde_json::json!({
+                "zones": zones,
+                "concentration": s.concentration
+            }))
         });
 
     // POST /biocore - Apply BioCore effects
@@ -123,13 +730,108 @@ async fn main() {
         .map(|input: BioCoreInput, state: Arc<Mutex<CityState>>| {
             let mut s = state.lock().unwrap();
             s.apply_biocore_effect(input);
-            
+
             warp::reply::json(&serde_json::json!({
                 "status": "success",
                 "message": "BioCore effect applied"
             }))
         });
 
+    // POST /biocore/batch - apply a batch of BioCore inputs as an atomic transaction
+    let biocore_batch_route = warp::path!("biocore" / "batch")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(state_filter.clone())
+        .map(|batch: BioCoreBatch, state: Arc<Mutex<CityState>>| {
+            let mut s = state.lock().unwrap();
+            let verdict = s.apply_biocore_batch(batch);
+            warp::reply::json(&verdict)
+        });
+
+    // POST /config - tune tick interval and charge/discharge rates at runtime
+    let config_route = warp::path("config")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(state_filter.clone())
+        .map(|update: ConfigUpdate, state: Arc<Mutex<CityState>>| {
+            let mut s = state.lock().unwrap();
+            s.configure(update);
+
+            warp::reply::json(&serde_json::json!({
+                "status": "success",
+                "tick_ms": s.tick_ms
+            }))
+        });
+
+    // GET /wards - recent ward events
+    let wards_get_route = warp::path("wards")
+        .and(warp::get())
+        .and(state_filter.clone())
+        .map(|state: Arc<Mutex<CityState>>| {
+            let s = state.lock().unwrap();
+            let events: Vec<_> = s.ward_events.iter().cloned().collect();
+            warp::reply::json(&events)
+        });
+
+    // POST /wards - register/enable a ward threshold by name
+    let wards_post_route = warp::path("wards")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(state_filter.clone())
+        .map(|config: WardConfig, state: Arc<Mutex<CityState>>| {
+            let mut s = state.lock().unwrap();
+            let found = s.register_ward_threshold(config);
+
+            warp::reply::json(&serde_json::json!({
+                "success": found
+            }))
+        });
+
+    // GET /concentration - current Herfindahl concentration index plus the
+    // audit log of corrective actions taken against over-represented zones
+    let concentration_get_route = warp::path("concentration")
+        .and(warp::get())
+        .and(state_filter.clone())
+        .map(|state: Arc<Mutex<CityState>>| {
+            let s = state.lock().unwrap();
+            let actions: Vec<_> = s.concentration_actions.iter().cloned().collect();
+            warp::reply::json(&serde_json::json!({
+                "concentration": s.concentration,
+                "max_concentration": s.max_concentration,
+                "mode": s.concentration_mode,
+                "actions": actions
+            }))
+        });
+
+    // GET /measurements?since=<tick> - measurement time series newer than `since`
+    let measurements_get_route = warp::path("measurements")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(state_filter.clone())
+        .map(|query: HashMap<String, String>, state: Arc<Mutex<CityState>>| {
+            let since = query.get("since").and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+            let s = state.lock().unwrap();
+            warp::reply::json(&s.measurements_since(since))
+        });
+
+    // POST /measurements/flush - write accumulated samples to NDJSON on disk
+    let measurements_flush_route = warp::path!("measurements" / "flush")
+        .and(warp::post())
+        .and(state_filter.clone())
+        .map(|state: Arc<Mutex<CityState>>| {
+            let s = state.lock().unwrap();
+            match s.flush_measurements("measurements.ndjson") {
+                Ok(count) => warp::reply::json(&serde_json::json!({
+                    "success": true,
+                    "samples_written": count
+                })),
+                Err(e) => warp::reply::json(&serde_json::json!({
+                    "success": false,
+                    "error": e.to_string()
+                })),
+            }
+        });
+
     // GET /health - Health check endpoint
     let health_route = warp::path("health")
         .and(warp::get())
@@ -141,19 +843,182 @@ async fn main() {
             }))
         });
 
+    // GET /subscribe - upgrades to a WebSocket and pushes zone-state deltas whenever a zone's state label changes
+    let subscribe_route = warp::path("subscribe")
+        .and(warp::ws())
+        .and(state_filter.clone())
+        .map(|ws: warp::ws::Ws, state: Arc<Mutex<CityState>>| {
+            ws.on_upgrade(move |socket| handle_subscriber(socket, state))
+        });
+
     // Combine all routes
     let routes = state_route
         .or(biocore_route)
+        .or(biocore_batch_route)
+        .or(config_route)
+        .or(wards_get_route)
+        .or(wards_post_route)
+        .or(concentration_get_route)
+        .or(measurements_get_route)
+        .or(measurements_flush_route)
         .or(health_route)
+        .or(subscribe_route)
         .with(warp::cors().allow_any_origin().allow_methods(vec!["GET", "POST"]));
 
     println!("ðŸ¦€ Rust city core running at http://localhost:3030");
     println!("ðŸ“Š Available endpoints:");
-    println!("   GET  /state   - Get city state");
-    println!("   POST /biocore - Apply BioCore effects");
-    println!("   GET  /health  - Health check");
+    println!("   GET  /state     - Get city state");
+    println!("   POST /biocore   - Apply BioCore effects");
+    println!("   POST /biocore/batch - Apply a batch of BioCore inputs atomically");
+    println!("   POST /config    - Tune tick interval and charge/discharge rates");
+    println!("   GET  /wards     - Recent ward events");
+    println!("   POST /wards     - Register/enable a ward threshold");
+    println!("   GET  /concentration - Concentration index and corrective action audit log");
+    println!("   GET  /measurements        - Measurement time series since a tick");
+    println!("   POST /measurements/flush  - Flush accumulated samples to NDJSON");
+    println!("   GET  /health    - Health check");
+    println!("   GET  /subscribe - WebSocket stream of zone-state deltas");
 
     warp::serve(routes)
         .run(([127, 0, 0, 1], 3030))
         .await;
 }
+
+async fn handle_subscriber(ws: warp::ws::WebSocket, state: Arc<Mutex<CityState>>) {
+    use futures_util::{SinkExt, StreamExt};
+
+    let (mut tx, mut rx) = ws.split();
+    let mut updates = {
+        let s = state.lock().unwrap();
+        s.tx.subscribe()
+    };
+
+    // Initial snapshot so a fresh subscriber isn't waiting on the next change.
+    let snapshot = {
+        let s = state.lock().unwrap();
+        s.snapshot()
+    };
+    if let Ok(payload) = serde_json::to_string(&snapshot) {
+        if tx.send(warp::ws::Message::text(payload)).await.is_err() {
+            return;
+        }
+    }
+
+    loop {
+        tokio::select! {
+            deltas = updates.recv() => {
+                match deltas {
+                    Ok(deltas) => {
+                        if let Ok(payload) = serde_json::to_string(&deltas) {
+                            if tx.send(warp::ws::Message::text(payload)).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            msg = rx.next() => {
+                if msg.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sustained_emergent_ward_fires_after_threshold_ticks() {
+        let mut state = CityState::new(2, 0.5, 0.02);
+        for zone in state.zones.iter_mut() {
+            zone.activity = 0.95; // EMERGENT (>= 0.7)
+        }
+        let mut ward = SustainedEmergentWard { threshold: 3, streak: 0 };
+
+        assert!(ward.check(&state).is_none());
+        assert!(ward.check(&state).is_none());
+        let event = ward.check(&state).expect("third consecutive emergent tick should fire");
+        assert_eq!(event.ward, "sustained_emergent");
+        assert_eq!(event.severity, "critical");
+    }
+
+    #[test]
+    fn sustained_emergent_ward_resets_streak_on_non_emergent_tick() {
+        let mut state = CityState::new(1, 0.5, 0.02);
+        state.zones[0].activity = 0.95;
+        let mut ward = SustainedEmergentWard { threshold: 2, streak: 0 };
+
+        assert!(ward.check(&state).is_none());
+        state.zones[0].activity = 0.5; // CALM/OVERSTIMULATED, breaks the streak
+        assert!(ward.check(&state).is_none());
+        state.zones[0].activity = 0.95;
+        assert!(ward.check(&state).is_none());
+    }
+
+    #[test]
+    fn stuck_at_bound_ward_flags_only_saturated_zones() {
+        let mut state = CityState::new(2, 0.5, 0.02);
+        state.zones[0].activity = 0.0;
+        state.zones[1].activity = 0.5;
+        let mut ward = StuckAtBoundWard { threshold: 2, streaks: HashMap::new() };
+
+        assert!(ward.check(&state).is_none());
+        let event = ward.check(&state).expect("zone stuck at 0.0 for two ticks should fire");
+        assert_eq!(event.zone_ids, vec![0]);
+        assert_eq!(event.severity, "warning");
+    }
+
+    #[test]
+    fn drift_ward_fires_only_outside_tolerance() {
+        let mut state = CityState::new(1, 0.5, 0.02);
+        state.zones[0].activity = 0.55;
+        let mut ward = DriftWard { tolerance: 0.1 };
+        assert!(ward.check(&state).is_none());
+
+        state.zones[0].activity = 0.9;
+        assert!(ward.check(&state).is_some());
+    }
+
+    #[test]
+    fn configure_updates_integral_max() {
+        let mut state = CityState::new(1, 0.5, 0.02);
+        assert_eq!(state.integral_max, DEFAULT_INTEGRAL_MAX);
+
+        state.configure(ConfigUpdate {
+            tick_ms: None,
+            charge_rate: None,
+            discharge_rate: None,
+            kp: None,
+            ki: None,
+            kd: None,
+            integral_max: Some(0.25),
+            max_concentration: None,
+            concentration_mode: None,
+        });
+
+        assert_eq!(state.integral_max, 0.25);
+    }
+
+    #[test]
+    fn homeostatic_update_clamps_integral_to_configured_band() {
+        let mut state = CityState::new(1, 1.0, 0.0);
+        state.kp = 0.0;
+        state.ki = 1.0;
+        state.kd = 0.0;
+        state.integral_max = 0.2;
+        state.zones[0].activity = 0.5; // not at a bound, so the integral guard doesn't freeze it
+        state.zones[0].charge_rate = 10.0; // don't let the zone rate clamp mask the integral clamp
+        state.ema[0] = 0.5;
+
+        for _ in 0..50 {
+            state.homeostatic_update();
+        }
+
+        assert!((state.pid[0].integral - 0.2).abs() < 1e-6);
+    }
+}
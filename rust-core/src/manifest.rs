@@ -0,0 +1,135 @@
+//! TOML manifests for zone topology and engine regulation parameters.
+//!
+//! Replaces the hardcoded `zones: usize` count and fixed `0.5` target with a
+//! data-driven `[engine]` table plus repeated `[[zone]]` entries, so
+//! operators can model a different city (zone count, names, initial
+//! activity, per-zone targets) without recompiling. A manifest may also
+//! define named `[profiles.*]` tables that override a subset of the base
+//! `[engine]` settings; the active profile is chosen by the caller (an env
+//! var or CLI flag), not baked into the file. Each `[[zone]]` entry may also
+//! list `neighbors = [ids...]`, wiring that zone into the reaction-diffusion
+//! coupling the engine applies before its PID correction.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::engine::HomeostaticConfig;
+
+#[derive(Debug, Deserialize)]
+struct ManifestDocument {
+    engine: EngineManifest,
+    #[serde(default)]
+    profiles: HashMap<String, EngineManifestOverride>,
+    #[serde(rename = "zone", default)]
+    zones: Vec<ZoneManifest>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct EngineManifest {
+    target_calmness: f64,
+    kp: f64,
+    #[serde(default)]
+    ki: f64,
+    #[serde(default)]
+    kd: f64,
+    #[serde(default = "default_integral_max")]
+    integral_max: f64,
+    #[serde(default = "default_dt")]
+    dt: f64,
+    #[serde(default)]
+    diffusion: f64,
+}
+
+fn default_integral_max() -> f64 {
+    1.0
+}
+
+fn default_dt() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct EngineManifestOverride {
+    target_calmness: Option<f64>,
+    kp: Option<f64>,
+    ki: Option<f64>,
+    kd: Option<f64>,
+    integral_max: Option<f64>,
+    dt: Option<f64>,
+    diffusion: Option<f64>,
+}
+
+impl EngineManifest {
+    fn apply_override(&mut self, over: &EngineManifestOverride) {
+        if let Some(v) = over.target_calmness {
+            self.target_calmness = v;
+        }
+        if let Some(v) = over.kp {
+            self.kp = v;
+        }
+        if let Some(v) = over.ki {
+            self.ki = v;
+        }
+        if let Some(v) = over.kd {
+            self.kd = v;
+        }
+        if let Some(v) = over.integral_max {
+            self.integral_max = v;
+        }
+        if let Some(v) = over.dt {
+            self.dt = v;
+        }
+        if let Some(v) = over.diffusion {
+            self.diffusion = v;
+        }
+    }
+}
+
+/// One `[[zone]]` entry: identity and initial state for a zone, as opposed
+/// to [`crate::zone::Zone::new`]'s random activity and fixed target.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ZoneManifest {
+    pub id: usize,
+    pub name: String,
+    pub initial_activity: f64,
+    pub target: Option<f64>,
+    #[serde(default)]
+    pub neighbors: Vec<usize>,
+}
+
+impl HomeostaticConfig {
+    /// Loads engine config and zone definitions from a TOML manifest.
+    ///
+    /// `profile`, if given, selects a `[profiles.<name>]` table whose
+    /// fields override the base `[engine]` table. With `None`, the profile is
+    /// taken from the `BHCS_PROFILE` env var, and the base settings are used
+    /// unmodified if that's unset too. A profile name the manifest doesn't
+    /// define is ignored.
+    pub fn from_toml(path: &str, profile: Option<&str>) -> Result<(Self, Vec<ZoneManifest>), String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read manifest {path}: {e}"))?;
+        let mut doc: ManifestDocument = toml::from_str(&contents)
+            .map_err(|e| format!("failed to parse manifest {path}: {e}"))?;
+
+        let profile = profile.map(str::to_string).or_else(|| std::env::var("BHCS_PROFILE").ok());
+        if let Some(profile) = profile {
+            if let Some(over) = doc.profiles.get(&profile) {
+                doc.engine.apply_override(over);
+            }
+        }
+
+        let config = HomeostaticConfig {
+            target_calmness: doc.engine.target_calmness,
+            kp: doc.engine.kp,
+            ki: doc.engine.ki,
+            kd: doc.engine.kd,
+            integral_max: doc.engine.integral_max,
+            dt: doc.engine.dt,
+            zones: doc.zones.len(),
+            diffusion: doc.engine.diffusion,
+        };
+
+        Ok((config, doc.zones))
+    }
+}
@@ -0,0 +1,62 @@
+//! CBOR snapshot persistence for [`HomeostaticEngine`] state.
+//!
+//! Zone activity, EMA buffers, PID accumulators, the neighbor graph, the
+//! state-transition version, and config all round-trip through an
+//! [`EngineSnapshot`] so a
+//! restart can rehydrate the engine via [`HomeostaticEngine::restore_from`]
+//! instead of randomizing zone activity from scratch. CBOR (not JSON) keeps
+//! the f64 fields exact and the files small enough for frequent autosave.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{HomeostaticConfig, HomeostaticEngine};
+use crate::zone::{PidController, Zone};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineSnapshot {
+    pub zones: Vec<Zone>,
+    #[serde(default)]
+    pub ema: Vec<f64>,
+    #[serde(default)]
+    pub pid_controllers: Vec<PidController>,
+    #[serde(default)]
+    pub neighbors: Vec<Vec<usize>>,
+    #[serde(default)]
+    pub version: u64,
+    pub config: HomeostaticConfig,
+}
+
+/// Serializes `snapshot` to CBOR and writes it to `path`.
+pub fn save(snapshot: &EngineSnapshot, path: &str) -> Result<(), String> {
+    let bytes = serde_cbor::to_vec(snapshot).map_err(|e| format!("failed to encode snapshot: {e}"))?;
+    std::fs::write(path, bytes).map_err(|e| format!("failed to write snapshot {path}: {e}"))
+}
+
+/// Reads and decodes a CBOR snapshot from `path`, or `None` if it doesn't exist.
+pub fn load(path: &str) -> Result<Option<EngineSnapshot>, String> {
+    if !std::path::Path::new(path).exists() {
+        return Ok(None);
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("failed to read snapshot {path}: {e}"))?;
+    let snapshot = serde_cbor::from_slice(&bytes)
+        .map_err(|e| format!("failed to decode snapshot {path}: {e}"))?;
+    Ok(Some(snapshot))
+}
+
+/// Spawns a background task that saves `engine` to `path` every `interval`,
+/// mirroring the tick-loop pattern used elsewhere for periodic background work.
+pub fn spawn_autosave(engine: Arc<Mutex<HomeostaticEngine>>, path: String, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let snapshot = engine.lock().unwrap().snapshot();
+            if let Err(e) = save(&snapshot, &path) {
+                eprintln!("autosave to {path} failed: {e}");
+            }
+        }
+    });
+}
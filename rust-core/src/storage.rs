@@ -0,0 +1,179 @@
+//! Persistent storage for zone snapshots, applied BioCore effects, and
+//! Luna's conversation/evolution history.
+//!
+//! Backed by `sqlx`'s database-agnostic `Any` driver so the same queries run
+//! against a local SQLite file in development and Postgres in production.
+//! Schema changes live in `migrations/` and are applied once at startup via
+//! `ZoneStore::connect`. `sqlx::migrate!` embeds one fixed directory at
+//! compile time, so the two dialects can't share a single migration set
+//! where their DDL genuinely differs (SQLite's `AUTOINCREMENT` integer
+//! primary key has no Postgres equivalent) -- we embed both directories and
+//! pick the matching one at runtime from the connection URL's scheme.
+
+use std::collections::HashMap;
+
+use sqlx::any::AnyPoolOptions;
+use sqlx::migrate::Migrator;
+use sqlx::{AnyPool, Row};
+
+use crate::fast_api_server::{ActiveEffect, ZoneData};
+use crate::luna_evolution::{Conversation, EvolutionMetrics};
+
+/// Default connection string used when `DATABASE_URL` isn't set — a local
+/// SQLite file so the server still persists state with zero configuration.
+pub const DEFAULT_DATABASE_URL: &str = "sqlite://luna_city.db";
+
+static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
+static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
+
+pub struct ZoneStore {
+    pool: AnyPool,
+}
+
+impl ZoneStore {
+    /// Connects to `database_url` (e.g. `sqlite://luna_city.db` or a
+    /// `postgres://...` URL) and applies any pending migrations.
+    pub async fn connect(database_url: &str) -> Result<Self, sqlx::Error> {
+        sqlx::any::install_default_drivers();
+        let pool = AnyPoolOptions::new()
+            .max_connections(5)
+            .connect(database_url)
+            .await?;
+
+        let store = Self { pool };
+        store.migrate(database_url).await?;
+        Ok(store)
+    }
+
+    // Postgres URLs use the `postgres://` or `postgresql://` scheme;
+    // everything else (plain paths, `sqlite://`) is treated as SQLite,
+    // matching `DEFAULT_DATABASE_URL`.
+    async fn migrate(&self, database_url: &str) -> Result<(), sqlx::Error> {
+        let migrator = if database_url.starts_with("postgres://") || database_url.starts_with("postgresql://") {
+            &POSTGRES_MIGRATOR
+        } else {
+            &SQLITE_MIGRATOR
+        };
+        migrator.run(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Loads every persisted zone snapshot, keyed by zone name.
+    pub async fn load_zones(&self) -> Result<HashMap<String, ZoneData>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT zone_name, activity_level, stress_level, population_density,
+                    primary_function, target, needs_json, last_updated
+             FROM zones",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut zones = HashMap::new();
+        for row in rows {
+            let zone_name: String = row.try_get("zone_name")?;
+            let needs_json: String = row.try_get("needs_json")?;
+            let last_updated: String = row.try_get("last_updated")?;
+
+            zones.insert(
+                zone_name.clone(),
+                ZoneData {
+                    zone_name,
+                    activity_level: row.try_get("activity_level")?,
+                    stress_level: row.try_get("stress_level")?,
+                    population_density: row.try_get("population_density")?,
+                    primary_function: row.try_get("primary_function")?,
+                    target: row.try_get("target")?,
+                    needs: serde_json::from_str(&needs_json).unwrap_or_default(),
+                    last_updated: last_updated
+                        .parse()
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                },
+            );
+        }
+
+        Ok(zones)
+    }
+
+    /// Upserts a zone's current snapshot.
+    pub async fn save_zone(&self, zone: &ZoneData) -> Result<(), sqlx::Error> {
+        let needs_json = serde_json::to_string(&zone.needs).unwrap_or_default();
+
+        sqlx::query(
+            "INSERT INTO zones
+                (zone_name, activity_level, stress_level, population_density, primary_function, target, needs_json, last_updated)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(zone_name) DO UPDATE SET
+                activity_level = excluded.activity_level,
+                stress_level = excluded.stress_level,
+                population_density = excluded.population_density,
+                primary_function = excluded.primary_function,
+                target = excluded.target,
+                needs_json = excluded.needs_json,
+                last_updated = excluded.last_updated",
+        )
+        .bind(&zone.zone_name)
+        .bind(zone.activity_level)
+        .bind(zone.stress_level)
+        .bind(zone.population_density)
+        .bind(&zone.primary_function)
+        .bind(zone.target)
+        .bind(needs_json)
+        .bind(zone.last_updated.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records a BioCore effect applied to `zone_name`.
+    pub async fn record_effect(&self, zone_name: &str, active: &ActiveEffect) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO biocore_effects
+                (zone_name, plant_name, drug_name, synergy_score, delta, duration_minutes, applied_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(zone_name)
+        .bind(&active.effect.plant_name)
+        .bind(&active.effect.drug_name)
+        .bind(active.effect.synergy_score)
+        .bind(active.effect.delta)
+        .bind(active.effect.duration_minutes as i64)
+        .bind(active.applied_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records a completed Luna conversation.
+    pub async fn record_conversation(&self, conversation: &Conversation) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO conversations
+                (id, timestamp, user_message, luna_response, effectiveness_score, learning_weight)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&conversation.id)
+        .bind(conversation.timestamp.to_rfc3339())
+        .bind(&conversation.user_message)
+        .bind(&conversation.luna_response)
+        .bind(conversation.effectiveness_score)
+        .bind(conversation.learning_weight)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records a snapshot of Luna's evolution metrics for historical queries.
+    pub async fn record_evolution_metrics(&self, metrics: &EvolutionMetrics) -> Result<(), sqlx::Error> {
+        let metrics_json = serde_json::to_string(metrics).unwrap_or_default();
+
+        sqlx::query("INSERT INTO evolution_metrics (recorded_at, metrics_json) VALUES (?, ?)")
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(metrics_json)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
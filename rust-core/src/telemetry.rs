@@ -0,0 +1,120 @@
+//! OpenTelemetry instrumentation for the fast API server.
+//!
+//! With the `otel` feature enabled, traces, metrics, and logs export over
+//! OTLP to the collector at `OTEL_EXPORTER_OTLP_ENDPOINT` (defaulting to
+//! `http://localhost:4317`), and [`Metrics`] exposes the counters/gauges
+//! handlers record into as they run. Without the feature, `init()` falls
+//! back to the plain stdout subscriber used before this module existed, and
+//! `Metrics`'s recording methods are no-ops, so local runs need no
+//! collector.
+
+#[cfg(feature = "otel")]
+mod otel {
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    use super::Metrics;
+
+    pub fn install() -> Metrics {
+        let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+            .unwrap_or_else(|_| "http://localhost:4317".to_string());
+
+        let tracer = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(&endpoint))
+            .install_batch(opentelemetry_sdk::runtime::Tokio)
+            .expect("failed to install OTLP trace pipeline");
+
+        let meter_provider = opentelemetry_otlp::new_pipeline()
+            .metrics(opentelemetry_sdk::runtime::Tokio)
+            .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(&endpoint))
+            .build()
+            .expect("failed to install OTLP metrics pipeline");
+        opentelemetry::global::set_meter_provider(meter_provider.clone());
+
+        tracing_subscriber::registry()
+            .with(tracing_subscriber::fmt::layer())
+            .with(tracing_opentelemetry::layer().with_tracer(tracer))
+            .init();
+
+        let meter = opentelemetry::global::meter("homeostatic_city_biocor");
+
+        Metrics {
+            activity_level: meter.f64_gauge("zone.activity_level").init(),
+            stress_level: meter.f64_gauge("zone.stress_level").init(),
+            processing_time_ms: meter.f64_histogram("luna.processing_time_ms").init(),
+            interactions_total: meter.u64_counter("luna.interactions_total").init(),
+            evolution_progress: meter.f64_gauge("luna.evolution_progress").init(),
+        }
+    }
+
+    pub(super) fn zone_attrs(zone_name: &str) -> [KeyValue; 1] {
+        [KeyValue::new("zone", zone_name.to_string())]
+    }
+
+    pub(super) fn interaction_attrs(interaction_type: &str) -> [KeyValue; 1] {
+        [KeyValue::new("interaction_type", interaction_type.to_string())]
+    }
+}
+
+#[cfg(feature = "otel")]
+pub struct Metrics {
+    activity_level: opentelemetry::metrics::Gauge<f64>,
+    stress_level: opentelemetry::metrics::Gauge<f64>,
+    processing_time_ms: opentelemetry::metrics::Histogram<f64>,
+    interactions_total: opentelemetry::metrics::Counter<u64>,
+    evolution_progress: opentelemetry::metrics::Gauge<f64>,
+}
+
+#[cfg(not(feature = "otel"))]
+pub struct Metrics;
+
+impl Metrics {
+    /// Records a zone's current `activity_level`/`stress_level` gauges.
+    pub fn record_zone(&self, zone_name: &str, activity_level: f64, stress_level: f64) {
+        #[cfg(feature = "otel")]
+        {
+            let attrs = otel::zone_attrs(zone_name);
+            self.activity_level.record(activity_level, &attrs);
+            self.stress_level.record(stress_level, &attrs);
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = (zone_name, activity_level, stress_level);
+    }
+
+    /// Records one Luna interaction: bumps the total counter and observes
+    /// its processing time.
+    pub fn record_interaction(&self, interaction_type: &str, processing_time_ms: u64) {
+        #[cfg(feature = "otel")]
+        {
+            let attrs = otel::interaction_attrs(interaction_type);
+            self.interactions_total.add(1, &attrs);
+            self.processing_time_ms.record(processing_time_ms as f64, &attrs);
+        }
+        #[cfg(not(feature = "otel"))]
+        let _ = (interaction_type, processing_time_ms);
+    }
+
+    /// Records Luna's current evolution progress gauge.
+    pub fn record_evolution_progress(&self, progress: f64) {
+        #[cfg(feature = "otel")]
+        self.evolution_progress.record(progress, &[]);
+        #[cfg(not(feature = "otel"))]
+        let _ = progress;
+    }
+}
+
+/// Installs the process-wide tracing subscriber and returns the metrics
+/// handle handlers should record into. Call once, before serving requests.
+#[cfg(feature = "otel")]
+pub fn init() -> Metrics {
+    otel::install()
+}
+
+#[cfg(not(feature = "otel"))]
+pub fn init() -> Metrics {
+    tracing_subscriber::fmt::init();
+    Metrics
+}
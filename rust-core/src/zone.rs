@@ -4,7 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ZoneState {
     Calm,
     Overstimulated,
@@ -29,23 +29,50 @@ impl ZoneState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Zone {
     id: usize,
+    #[serde(default)]
+    name: Option<String>,
     activity: f64,
     target: f64,
     state: ZoneState,
     last_update: i64,
+    // The engine's global `version` counter at the last tick this zone's
+    // `state` actually transitioned, so `/watch` can tell which zones
+    // changed since a client's last-seen version without re-diffing every
+    // zone's full history.
+    #[serde(default)]
+    state_version: u64,
 }
 
 impl Zone {
     pub fn new(id: usize) -> Self {
         let activity = rand::random::<f64>() * 0.6 + 0.2; // Random initial activity
         let state = ZoneState::from_activity(activity);
-        
+
         Self {
             id,
+            name: None,
             activity,
             target: 0.5, // Homeostatic target
             state,
             last_update: chrono::Utc::now().timestamp(),
+            state_version: 0,
+        }
+    }
+
+    /// Builds a zone from a manifest entry instead of [`Self::new`]'s
+    /// random activity and fixed `0.5` target.
+    pub fn from_manifest(id: usize, name: String, initial_activity: f64, target: f64) -> Self {
+        let activity = initial_activity.clamp(0.0, 1.0);
+        let state = ZoneState::from_activity(activity);
+
+        Self {
+            id,
+            name: Some(name),
+            activity,
+            target,
+            state,
+            last_update: chrono::Utc::now().timestamp(),
+            state_version: 0,
         }
     }
 
@@ -53,6 +80,10 @@ impl Zone {
         self.id
     }
 
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     pub fn activity(&self) -> f64 {
         self.activity
     }
@@ -65,6 +96,16 @@ impl Zone {
         self.target
     }
 
+    pub fn state_version(&self) -> u64 {
+        self.state_version
+    }
+
+    /// Stamps this zone with the engine's `version` counter; called by
+    /// [`crate::HomeostaticEngine::update`] right after it detects a transition.
+    pub fn mark_transitioned(&mut self, version: u64) {
+        self.state_version = version;
+    }
+
     pub fn apply_adjustment(&mut self, adjustment: f64) {
         self.activity = (self.activity + adjustment).clamp(0.0, 1.0);
         self.state = ZoneState::from_activity(self.activity);
@@ -83,3 +124,116 @@ impl Zone {
         self.last_update = chrono::Utc::now().timestamp();
     }
 }
+
+/// Drives a value back toward a target deterministically via proportional,
+/// integral, and derivative terms, with the integral clamped to avoid windup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PidController {
+    pub kp: f64,
+    pub ki: f64,
+    pub kd: f64,
+    integral: f64,
+    prev_error: f64,
+    integral_max: f64,
+}
+
+impl PidController {
+    pub fn new(kp: f64, ki: f64, kd: f64) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            integral: 0.0,
+            prev_error: 0.0,
+            integral_max: 1.0,
+        }
+    }
+
+    /// Like [`Self::new`] but with a configurable anti-windup clamp instead
+    /// of the default `1.0`.
+    pub fn with_integral_max(kp: f64, ki: f64, kd: f64, integral_max: f64) -> Self {
+        Self {
+            integral_max,
+            ..Self::new(kp, ki, kd)
+        }
+    }
+
+    /// Computes the next adjustment for `current` relative to `target`,
+    /// updating the controller's internal integral/derivative state.
+    pub fn step(&mut self, current: f64, target: f64) -> f64 {
+        let error = target - current;
+        self.integral = (self.integral + error).clamp(-self.integral_max, self.integral_max);
+        let derivative = error - self.prev_error;
+        self.prev_error = error;
+
+        self.kp * error + self.ki * self.integral + self.kd * derivative
+    }
+
+    /// Like [`Self::step`] but scales the integral and derivative terms by
+    /// a tick duration `dt` instead of assuming `dt == 1`.
+    pub fn step_dt(&mut self, current: f64, target: f64, dt: f64) -> f64 {
+        let error = target - current;
+        self.integral = (self.integral + error * dt).clamp(-self.integral_max, self.integral_max);
+        let derivative = (error - self.prev_error) / dt;
+        self.prev_error = error;
+
+        self.kp * error + self.ki * self.integral + self.kd * derivative
+    }
+}
+
+impl Default for PidController {
+    fn default() -> Self {
+        Self::new(0.1, 0.0, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proportional_only_pushes_toward_target() {
+        let mut pid = PidController::new(0.5, 0.0, 0.0);
+        let adjustment = pid.step(0.2, 0.5);
+        assert!((adjustment - 0.15).abs() < 1e-9);
+    }
+
+    #[test]
+    fn integral_accumulates_under_sustained_error() {
+        let mut pid = PidController::new(0.0, 0.1, 0.0);
+        let first = pid.step(0.0, 1.0);
+        let second = pid.step(0.0, 1.0);
+        // Constant error keeps accumulating in the integral term, so the
+        // adjustment should grow tick over tick.
+        assert!(second > first);
+    }
+
+    #[test]
+    fn integral_clamps_to_configured_band() {
+        let mut pid = PidController::with_integral_max(0.0, 1.0, 0.0, 0.3);
+        for _ in 0..100 {
+            pid.step(0.0, 1.0);
+        }
+        // ki == 1.0, so the adjustment equals the clamped integral directly.
+        let adjustment = pid.step(0.0, 1.0);
+        assert!((adjustment - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn step_dt_with_unit_dt_matches_step() {
+        let mut a = PidController::new(0.0, 1.0, 1.0);
+        let mut b = PidController::new(0.0, 1.0, 1.0);
+
+        let step_a = a.step(0.0, 1.0);
+        let step_b = b.step_dt(0.0, 1.0, 1.0);
+        assert!((step_a - step_b).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zone_state_thresholds() {
+        assert_eq!(ZoneState::from_activity(0.1), ZoneState::Calm);
+        assert_eq!(ZoneState::from_activity(0.5), ZoneState::Overstimulated);
+        assert_eq!(ZoneState::from_activity(0.8), ZoneState::Emergent);
+        assert_eq!(ZoneState::from_activity(0.95), ZoneState::Critical);
+    }
+}